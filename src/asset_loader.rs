@@ -6,6 +6,12 @@ pub struct SceneAssets {
     pub spaceship: Handle<Scene>,
     pub missiles: Handle<Scene>,
     pub satellite: Handle<Scene>,
+    pub shield_bubble: Handle<Mesh>,
+    pub shield_material: Handle<StandardMaterial>,
+    pub beam: Handle<Mesh>,
+    pub beam_material: Handle<StandardMaterial>,
+    pub saucer_material: Handle<StandardMaterial>,
+    pub power_up_material: Handle<StandardMaterial>,
 }
 
 pub struct AssetLoaderPlugin;
@@ -17,11 +23,43 @@ impl Plugin for AssetLoaderPlugin {
     }
 }
 
-fn load_assets(mut scene_assets: ResMut<SceneAssets>, asset_server: Res<AssetServer>) {
+fn load_assets(
+    mut scene_assets: ResMut<SceneAssets>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
     *scene_assets = SceneAssets {
         spaceship: asset_server.load("Spaceship.glb#Scene0"),
         asteroid: asset_server.load("Planet.glb#Scene0"),
         missiles: asset_server.load("Bush.glb#Scene0"),
         satellite: asset_server.load("Base Large.glb#Scene0"),
+        shield_bubble: meshes.add(Sphere::new(1.0)),
+        shield_material: materials.add(StandardMaterial {
+            base_color: Color::rgba(0.3, 0.7, 1.0, 0.25),
+            emissive: Color::rgb(0.1, 0.3, 0.6),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
+        beam: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        beam_material: materials.add(StandardMaterial {
+            base_color: Color::rgb(1.0, 0.3, 0.2),
+            emissive: Color::rgb(4.0, 1.0, 0.5),
+            unlit: true,
+            ..default()
+        }),
+        saucer_material: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.6, 0.65, 0.7),
+            emissive: Color::rgb(0.6, 0.1, 0.1),
+            metallic: 0.8,
+            perceptual_roughness: 0.3,
+            ..default()
+        }),
+        power_up_material: materials.add(StandardMaterial {
+            base_color: Color::rgba(1.0, 0.85, 0.2, 0.6),
+            emissive: Color::rgb(2.0, 1.5, 0.3),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
     }
 }
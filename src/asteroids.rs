@@ -1,23 +1,124 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use rand::{rngs::ThreadRng, Rng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::ops::Range;
 
 use crate::{
     asset_loader::SceneAssets,
-    collision::CollisionDamage,
-    despawn::DespawnOnDie,
-    health::Health,
+    collision::{CollisionDamage, Faction},
+    despawn::{DeathReaderSet, DespawnOnDie},
+    director::{Director, DirectorTuning},
+    health::{Armor, DamageKind, DieEvent, Health},
     movement::{
-        Acceleration, AngularVelocity, ConfinedToPlayArea, MovingObjectBundle, Velocity, WORLD_SIZE,
+        Acceleration, AngularVelocity, ConfinedToPlayArea, MovementSet, MovementTuning,
+        MovingObjectBundle, Velocity,
     },
+    rng::GameRng,
     schedule::InGameSet,
+    wave::Wave,
 };
 
 const VELOCITY_SCALAR: f32 = 5.0;
 const ACCELERATION_SCALAR: f32 = 0.0;
 const SPAWN_TIME_SECONDS: f32 = 2.5;
 const ASTEROID_HEALTH_RANGE: Range<f32> = 5.0..20.0;
+/// The radius of an asteroid's collider before it's scaled.
+const ASTEROID_RADIUS: f32 = 2.5;
+
+/// Balance numbers for asteroid spawning, loaded from the tuning file.  See [crate::tuning].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AsteroidTuning {
+    /// How fast the first wave's asteroids are.  See [crate::wave::WaveTuning] for the waves after it.
+    pub velocity_scalar: f32,
+    pub acceleration_scalar: f32,
+    /// How long between each of a wave's asteroids coming in, before the [Director] speeds it up or slows it down.
+    pub spawn_time_seconds: f32,
+    /// How much health the first wave's asteroids have.
+    pub health_range: Range<f32>,
+    /// Asteroids that started with less health than this shatter instead of splitting.
+    pub min_split_health: f32,
+    pub min_fragments: u32,
+    pub max_fragments: u32,
+    /// The share of its parent's starting health each fragment gets.
+    pub fragment_health_fraction: f32,
+    /// The share of its parent's velocity each fragment carries on with.
+    pub fragment_momentum: f32,
+    /// How fast fragments fly apart from each other.
+    pub fragment_speed: f32,
+    /// The types of asteroid that come in.  Fragments are the same type as the asteroid they broke off.
+    pub types: Vec<AsteroidType>,
+}
+
+impl Default for AsteroidTuning {
+    fn default() -> Self {
+        Self {
+            velocity_scalar: VELOCITY_SCALAR,
+            acceleration_scalar: ACCELERATION_SCALAR,
+            spawn_time_seconds: SPAWN_TIME_SECONDS,
+            health_range: ASTEROID_HEALTH_RANGE,
+            min_split_health: 6.0,
+            min_fragments: 2,
+            max_fragments: 3,
+            fragment_health_fraction: 0.5,
+            fragment_momentum: 0.6,
+            fragment_speed: 6.0,
+            types: vec![
+                AsteroidType {
+                    name: "Rock".to_string(),
+                    weight: 4.0,
+                    armor: Armor::default(),
+                },
+                // Shrugs off small shots, but cracks under explosives.
+                AsteroidType {
+                    name: "Iron".to_string(),
+                    weight: 1.0,
+                    armor: Armor {
+                        flat: 1.5,
+                        resistances: vec![
+                            (DamageKind::Kinetic, 0.5),
+                            (DamageKind::Explosive, -0.5),
+                        ],
+                    },
+                },
+            ],
+        }
+    }
+}
+
+/// One type of asteroid, and how it stands up to damage.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AsteroidType {
+    pub name: String,
+    /// How likely this type is to come in, compared with the others.
+    pub weight: f32,
+    pub armor: Armor,
+}
+
+impl AsteroidTuning {
+    /// Pick an asteroid type by weight, or [None] if there aren't any to pick from.
+    pub fn pick_type(&self, rng: &mut impl Rng) -> Option<&AsteroidType> {
+        let total: f32 = self
+            .types
+            .iter()
+            .map(|asteroid_type| asteroid_type.weight.max(0.0))
+            .sum();
+        if total <= 0.0 {
+            return self.types.first();
+        }
+
+        let mut pick = rng.gen_range(0.0..total);
+        for asteroid_type in &self.types {
+            let weight = asteroid_type.weight.max(0.0);
+            if pick < weight {
+                return Some(asteroid_type);
+            }
+            pick -= weight;
+        }
+        self.types.last()
+    }
+}
 
 /// Function to scale the asteroid with its health.
 fn scale_from_health(health: f32) -> f32 {
@@ -30,72 +131,94 @@ fn collision_damage_from_health(health: f32) -> f32 {
 }
 
 #[derive(Component, Debug)]
-pub struct Asteroid;
-
-#[derive(Resource, Debug)]
-pub struct SpawnTimer {
-    timer: Timer,
+pub struct Asteroid {
+    /// How much health the asteroid started with, which decides how big its fragments are.
+    pub starting_health: f32,
+    /// Zero for asteroids that flew in, one for their fragments and so on.
+    pub generation: u32,
 }
 
 pub struct AsteroidPlugin;
 
 impl Plugin for AsteroidPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(SpawnTimer {
-            timer: Timer::from_seconds(SPAWN_TIME_SECONDS, TimerMode::Repeating),
-        })
-        .add_systems(
-            Update,
-            (
-                spawn_asteroid,
-                asteroids_scale_with_health,
-                confine_once_in_play_area,
+        app.init_resource::<AsteroidTuning>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    spawn_asteroid,
+                    asteroids_scale_with_health,
+                    confine_once_in_play_area,
+                )
+                    .chain()
+                    .after(MovementSet)
+                    .in_set(InGameSet::EntityUpdates),
             )
-                .in_set(InGameSet::EntityUpdates),
-        );
+            .add_systems(
+                FixedUpdate,
+                split_destroyed_asteroids.in_set(DeathReaderSet),
+            );
     }
 }
 
+/// Send in the current wave's asteroids one at a time, whenever the [Director] says so.
 fn spawn_asteroid(
     mut commands: Commands,
-    mut spawn_timer: ResMut<SpawnTimer>,
-    time: Res<Time>,
+    mut wave: ResMut<Wave>,
+    mut director: ResMut<Director>,
+    (director_tuning, tuning): (Res<DirectorTuning>, Res<AsteroidTuning>),
     asset_server: Res<SceneAssets>,
+    mut rng: ResMut<GameRng>,
+    movement_tuning: Res<MovementTuning>,
 ) {
-    // Check if we're ready to spawn a new asteroid yet.
-    spawn_timer.timer.tick(time.delta());
-    if !spawn_timer.timer.just_finished() {
+    if !wave.is_spawning() || !director.take_spawn(&director_tuning) {
         return;
     }
+    wave.asteroids_to_spawn -= 1;
 
-    let mut rng = rand::thread_rng();
+    let rng = rng.as_mut();
+    let world_size = movement_tuning.world_size;
 
     // Spawn the asteroid somewhere out of the game area.
     let translation = loop {
-        let position = random_2d_unit_vector(&mut rng) * 100.0;
-        if position.distance(Vec3::ZERO) > (WORLD_SIZE * 1.5) {
+        let position = random_2d_unit_vector(rng) * (world_size * 2.0);
+        if position.distance(Vec3::ZERO) > (world_size * 1.5) {
             break position;
         }
     };
 
     // Have the asteroid moving towards the middle of the game area.
-    let velocity = ((random_2d_unit_vector(&mut rng) * (WORLD_SIZE * 0.75)) - translation)
+    let velocity = ((random_2d_unit_vector(rng) * (world_size * 0.75)) - translation)
         .normalize_or_zero()
-        * VELOCITY_SCALAR;
+        * wave.definition.velocity_scalar;
+
+    let acceleration = random_2d_unit_vector(rng) * wave.definition.acceleration_scalar;
+    let angular_velocity = random_2d_unit_vector(rng);
 
-    let acceleration = random_2d_unit_vector(&mut rng) * ACCELERATION_SCALAR;
-    let angular_velocity = random_2d_unit_vector(&mut rng);
+    // A range that's been tuned down to nothing just means every asteroid is the same.
+    let health_range = wave.definition.health_range.clone();
+    let health = if health_range.is_empty() {
+        health_range.start
+    } else {
+        rng.gen_range(health_range)
+    } * director.toughness;
 
-    let health = rng.gen_range(ASTEROID_HEALTH_RANGE);
+    let armor = tuning
+        .pick_type(rng)
+        .map(|asteroid_type| asteroid_type.armor.clone())
+        .unwrap_or_default();
 
-    commands.spawn(AsteroidBundle::new(
-        asset_server.asteroid.clone(),
-        translation,
-        velocity,
-        acceleration,
-        angular_velocity,
-        health,
-    ));
+    commands.spawn(
+        AsteroidBundle::new(
+            asset_server.asteroid.clone(),
+            translation,
+            velocity,
+            acceleration,
+            angular_velocity,
+            health,
+        )
+        .with_armor(armor),
+    );
 }
 
 #[derive(Bundle)]
@@ -103,6 +226,7 @@ pub struct AsteroidBundle {
     pub moving_object_bundle: MovingObjectBundle,
     pub asteroid: Asteroid,
     pub health: Health,
+    pub armor: Armor,
     pub collision_damage: CollisionDamage,
     pub active_events: ActiveEvents,
     pub despawn_on_die: DespawnOnDie,
@@ -126,7 +250,8 @@ impl AsteroidBundle {
                 transform,
                 ..Default::default()
             },
-            Collider::ball(2.5),
+            Collider::ball(ASTEROID_RADIUS),
+            Faction::Hazard,
             velocity,
             acceleration,
             angular_velocity,
@@ -134,8 +259,12 @@ impl AsteroidBundle {
 
         AsteroidBundle {
             moving_object_bundle,
-            asteroid: Asteroid,
+            asteroid: Asteroid {
+                starting_health: health,
+                generation: 0,
+            },
             health: Health::new(health),
+            armor: Armor::default(),
             collision_damage: CollisionDamage::new(health),
             active_events: ActiveEvents::COLLISION_EVENTS,
             despawn_on_die: DespawnOnDie,
@@ -143,6 +272,85 @@ impl AsteroidBundle {
     }
 }
 
+impl AsteroidBundle {
+    /// A fragment of a bigger asteroid, one generation on from it.
+    pub fn fragment(
+        model: Handle<Scene>,
+        parent: &Asteroid,
+        translation: Vec3,
+        velocity: Vec3,
+        angular_velocity: Vec3,
+        health: f32,
+    ) -> AsteroidBundle {
+        let mut bundle = AsteroidBundle::new(
+            model,
+            translation,
+            velocity,
+            Vec3::ZERO,
+            angular_velocity,
+            health,
+        );
+        bundle.asteroid.generation = parent.generation + 1;
+        bundle
+    }
+
+    /// The asteroid with its type's armor.
+    pub fn with_armor(mut self, armor: Armor) -> Self {
+        self.armor = armor;
+        self
+    }
+}
+
+/// Big asteroids break into two or three smaller ones when they're destroyed, which fly apart while carrying on
+/// with some of the parent's momentum.  Small ones just shatter.
+pub(crate) fn split_destroyed_asteroids(
+    mut commands: Commands,
+    mut die_events: EventReader<DieEvent>,
+    asteroids: Query<(&Asteroid, &Transform, &Velocity, Option<&Armor>)>,
+    asset_server: Res<SceneAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<AsteroidTuning>,
+) {
+    let rng = rng.as_mut();
+
+    for DieEvent { entity, .. } in die_events.read() {
+        let Ok((asteroid, transform, velocity, armor)) = asteroids.get(*entity) else {
+            continue;
+        };
+        if asteroid.starting_health < tuning.min_split_health {
+            continue;
+        }
+
+        let fragments =
+            rng.gen_range(tuning.min_fragments..=tuning.max_fragments.max(tuning.min_fragments));
+        if fragments == 0 {
+            continue;
+        }
+        let health = asteroid.starting_health * tuning.fragment_health_fraction;
+
+        // Spread the fragments evenly around the parent, far enough apart that they don't start off touching.
+        let radius = ASTEROID_RADIUS * scale_from_health(health);
+        let first_angle = rng.gen_range(0.0..std::f32::consts::TAU);
+
+        for i in 0..fragments {
+            let angle = first_angle + std::f32::consts::TAU * i as f32 / fragments as f32;
+            let direction = Vec3::new(angle.cos(), 0.0, angle.sin());
+
+            commands.spawn(
+                AsteroidBundle::fragment(
+                    asset_server.asteroid.clone(),
+                    asteroid,
+                    transform.translation + direction * radius * 1.2,
+                    velocity.value * tuning.fragment_momentum + direction * tuning.fragment_speed,
+                    random_2d_unit_vector(rng),
+                    health,
+                )
+                .with_armor(armor.cloned().unwrap_or_default()),
+            );
+        }
+    }
+}
+
 /// The physical size of the asteroids and the amount of damage they do on collision scales by how much health they have.
 fn asteroids_scale_with_health(
     mut query: Query<
@@ -151,13 +359,15 @@ fn asteroids_scale_with_health(
     >,
 ) {
     for (mut transform, mut collision_damage, health) in query.iter_mut() {
-        collision_damage.amount = collision_damage_from_health(health.value);
-        transform.scale = Vec3::splat(scale_from_health(health.value));
+        // Overhealed asteroids don't grow any bigger than they started.
+        let health = health.value.min(health.max);
+        collision_damage.amount = collision_damage_from_health(health);
+        transform.scale = Vec3::splat(scale_from_health(health));
     }
 }
 
 /// Generate a random XZ vector with length 1.0.
-fn random_2d_unit_vector(rng: &mut ThreadRng) -> Vec3 {
+pub fn random_2d_unit_vector(rng: &mut impl Rng) -> Vec3 {
     Vec3::new(rng.gen_range(-1.0..1.0), 0.0, rng.gen_range(-1.0..1.0)).normalize_or_zero()
 }
 
@@ -172,9 +382,10 @@ fn confine_once_in_play_area(
         ),
     >,
     mut commands: Commands,
+    tuning: Res<MovementTuning>,
 ) {
     for (entity, transform) in query.iter() {
-        if transform.translation.distance(Vec3::ZERO) <= WORLD_SIZE {
+        if transform.translation.distance(Vec3::ZERO) <= tuning.world_size {
             commands.entity(entity).insert(ConfinedToPlayArea);
         }
     }
@@ -12,7 +12,12 @@ impl Plugin for CameraPlugin {
 
 fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, CAMERA_DISTANCE, 0.0).looking_at(Vec3::ZERO, Vec3::Z),
+        transform: camera_transform(),
         ..Default::default()
     });
 }
+
+/// Where the camera sits, looking down on the play area.
+pub fn camera_transform() -> Transform {
+    Transform::from_xyz(0.0, CAMERA_DISTANCE, 0.0).looking_at(Vec3::ZERO, Vec3::Z)
+}
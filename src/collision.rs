@@ -1,14 +1,155 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
+use bevy_rapier3d::{parry::query::intersection_test, rapier::geometry::CollisionEventFlags};
+use serde::{Deserialize, Serialize};
 
-use crate::health::Health;
+use crate::{
+    health::{DamageEvent, DamageKind, Health},
+    movement::{AngularVelocity, MovementSet, TransformInterpolation, Velocity},
+    schedule::InGameSet,
+};
 
-/// Handles collision events sent by the Rapier physics plugin.
+/// Keeps each entity's collision groups in line with its [Faction], and handles collision events sent by the Rapier
+/// physics plugin.
 pub struct CollisionPlugin;
 
 impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, apply_collision_damage);
+        app.init_resource::<CollisionTuning>()
+            .add_systems(
+                FixedUpdate,
+                // Entities spawned during the tick get their groups before physics first sees them.
+                update_collision_groups
+                    .after(InGameSet::EntityUpdates)
+                    .before(PhysicsSet::SyncBackend),
+            )
+            .add_systems(
+                FixedUpdate,
+                sweep_fast_projectiles
+                    .after(MovementSet)
+                    .in_set(InGameSet::EntityUpdates),
+            )
+            .add_systems(
+                FixedUpdate,
+                (apply_collision_damage, exchange_momentum).in_set(InGameSet::CollisionDetection),
+            );
+    }
+}
+
+/// Which side an entity is on, which decides what it can collide with.  See [CollisionTuning].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Faction {
+    Player,
+    PlayerProjectile,
+    Hazard,
+    Enemy,
+    EnemyProjectile,
+    Pickup,
+}
+
+impl Faction {
+    pub const ALL: [Faction; 6] = [
+        Faction::Player,
+        Faction::PlayerProjectile,
+        Faction::Hazard,
+        Faction::Enemy,
+        Faction::EnemyProjectile,
+        Faction::Pickup,
+    ];
+
+    /// The Rapier collision group the faction belongs to.
+    fn group(self) -> Group {
+        let index = Faction::ALL
+            .iter()
+            .position(|faction| *faction == self)
+            .unwrap_or(0);
+        Group::from_bits_truncate(1 << index)
+    }
+
+    /// Whether the faction is on the player's side.
+    pub fn is_players(self) -> bool {
+        matches!(self, Faction::Player | Faction::PlayerProjectile)
+    }
+}
+
+/// Which factions collide with each other and how hard they bounce, loaded from the tuning file.  See
+/// [crate::tuning].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CollisionTuning {
+    /// The pairs of factions that collide.  It works both ways round, so each pair only needs listing once, and
+    /// any pair that isn't listed passes straight through each other.
+    pub interactions: Vec<(Faction, Faction)>,
+    /// The pairs of factions that bounce off each other without doing any damage.
+    pub harmless: Vec<(Faction, Faction)>,
+    /// How much of the speed they hit each other with two things keep when they bounce apart, from 0.0 to 1.0.
+    pub restitution: f32,
+    /// How fast a glancing blow sets things spinning, in radians per second for each unit of sideways speed.
+    pub spin: f32,
+}
+
+impl Default for CollisionTuning {
+    fn default() -> Self {
+        use Faction::*;
+
+        Self {
+            interactions: vec![
+                (Player, Hazard),
+                (Player, Enemy),
+                (Player, EnemyProjectile),
+                (Player, Pickup),
+                (PlayerProjectile, Hazard),
+                (PlayerProjectile, Enemy),
+                (PlayerProjectile, EnemyProjectile),
+                (Hazard, Hazard),
+                (Hazard, Enemy),
+                (Hazard, EnemyProjectile),
+            ],
+            harmless: vec![(Hazard, Hazard)],
+            restitution: 0.8,
+            spin: 0.05,
+        }
+    }
+}
+
+impl CollisionTuning {
+    /// Whether two factions collide.
+    pub fn interacts(&self, a: Faction, b: Faction) -> bool {
+        contains_pair(&self.interactions, a, b)
+    }
+
+    /// Whether two factions that collide damage each other.
+    pub fn damages(&self, a: Faction, b: Faction) -> bool {
+        self.interacts(a, b) && !contains_pair(&self.harmless, a, b)
+    }
+
+    /// The Rapier collision groups for an entity of the given faction.
+    pub fn collision_groups(&self, faction: Faction) -> CollisionGroups {
+        let filters = Faction::ALL
+            .into_iter()
+            .filter(|other| self.interacts(faction, *other))
+            .fold(Group::NONE, |filters, other| filters | other.group());
+
+        CollisionGroups::new(faction.group(), filters)
+    }
+}
+
+/// Whether a list of pairs has the two factions in it, either way round.
+fn contains_pair(pairs: &[(Faction, Faction)], a: Faction, b: Faction) -> bool {
+    pairs
+        .iter()
+        .any(|&(first, second)| (first, second) == (a, b) || (first, second) == (b, a))
+}
+
+/// Put new entities in their faction's collision groups, and everything in again whenever the tuning changes.
+fn update_collision_groups(
+    mut query: Query<(Ref<Faction>, &mut CollisionGroups)>,
+    tuning: Res<CollisionTuning>,
+) {
+    for (faction, mut collision_groups) in query.iter_mut() {
+        if tuning.is_changed() || faction.is_changed() {
+            collision_groups.set_if_neq(tuning.collision_groups(*faction));
+        }
     }
 }
 
@@ -16,11 +157,19 @@ impl Plugin for CollisionPlugin {
 #[derive(Debug, Component)]
 pub struct CollisionDamage {
     pub amount: f32,
+    pub kind: DamageKind,
 }
 
 impl CollisionDamage {
     pub fn new(amount: f32) -> Self {
-        CollisionDamage { amount }
+        CollisionDamage {
+            amount,
+            kind: DamageKind::Collision,
+        }
+    }
+
+    pub fn with_kind(self, kind: DamageKind) -> Self {
+        CollisionDamage { kind, ..self }
     }
 }
 
@@ -36,11 +185,110 @@ impl AsRef<f32> for CollisionDamage {
     }
 }
 
-/// Check for and apply collision damage when relevant collisions happen.
+/// When an entity with this component hits something, it pushes it away with this much momentum instead of
+/// bouncing off it.
+#[derive(Debug, Component)]
+pub struct CollisionPush {
+    pub momentum: f32,
+}
+
+impl CollisionPush {
+    pub fn new(momentum: f32) -> Self {
+        CollisionPush { momentum }
+    }
+}
+
+/// Marks a projectile that can move further in a tick than it is wide, so its path is swept for hits instead of
+/// only checking where it ends up.
+#[derive(Debug, Component)]
+pub struct FastProjectile;
+
+/// Send collision events for everything fast projectiles flew through this tick without ending up touching, which
+/// physics alone would miss.  Whatever they already touched or end up touching gets its events from physics as
+/// usual, so nothing is hit twice.
+fn sweep_fast_projectiles(
+    mut collision_events: EventWriter<CollisionEvent>,
+    projectiles: Query<
+        (
+            Entity,
+            &Transform,
+            &TransformInterpolation,
+            &Collider,
+            &Faction,
+        ),
+        With<FastProjectile>,
+    >,
+    colliders: Query<(&Transform, &Collider)>,
+    rapier_context: Res<RapierContext>,
+    tuning: Res<CollisionTuning>,
+) {
+    for (entity, transform, interpolation, collider, faction) in projectiles.iter() {
+        let start = interpolation.previous();
+        let displacement = transform.translation - start.translation;
+        if displacement == Vec3::ZERO {
+            continue;
+        }
+
+        // A projectile's own collision groups aren't filled in until after its first tick, so go by its faction.
+        let collision_groups = tuning.collision_groups(*faction);
+
+        // Each cast finds the first thing in the way, so keep casting past everything found until the path is clear.
+        let mut hits = Vec::new();
+        loop {
+            let not_hit_yet = |other: Entity| !hits.contains(&other);
+            let filter = QueryFilter::new()
+                .groups(collision_groups)
+                .exclude_collider(entity)
+                .predicate(&not_hit_yet);
+            let Some((hit, _)) = rapier_context.cast_shape(
+                start.translation,
+                start.rotation,
+                displacement,
+                collider,
+                1.0,
+                true,
+                filter,
+            ) else {
+                break;
+            };
+            hits.push(hit);
+        }
+
+        for hit in hits {
+            if rapier_context.intersection_pair(entity, hit) == Some(true) {
+                continue;
+            }
+            let Ok((other_transform, other_collider)) = colliders.get(hit) else {
+                continue;
+            };
+            let touching_at_end = intersection_test(
+                &(transform.translation, transform.rotation).into(),
+                &*collider.raw,
+                &(other_transform.translation, other_transform.rotation).into(),
+                &*other_collider.raw,
+            )
+            .unwrap_or(false);
+            if touching_at_end {
+                continue;
+            }
+
+            collision_events.send(CollisionEvent::Started(
+                entity,
+                hit,
+                CollisionEventFlags::SENSOR,
+            ));
+        }
+    }
+}
+
+/// Turn collisions between entities that damage each other into [DamageEvent]s.
 fn apply_collision_damage(
     mut collision_event_reader: EventReader<CollisionEvent>,
-    mut health_query: Query<&mut Health>,
+    mut damage_events: EventWriter<DamageEvent>,
+    health_query: Query<(), With<Health>>,
     collision_damage_query: Query<&CollisionDamage>,
+    faction_query: Query<&Faction>,
+    tuning: Res<CollisionTuning>,
 ) {
     for event in collision_event_reader.read() {
         // We only care about collisions that have just started.
@@ -48,38 +296,130 @@ fn apply_collision_damage(
             continue;
         };
 
+        // Collision groups keep most of these from ever happening, but they can be out of date for a tick after
+        // the tuning changes.
+        if let (Ok(faction1), Ok(faction2)) =
+            (faction_query.get(*entity1), faction_query.get(*entity2))
+        {
+            if !tuning.damages(*faction1, *faction2) {
+                continue;
+            }
+        }
+
         // Because only one collision event is generated for each collision, we need to check both entities for damage.
-        try_damage(
-            *entity1,
-            *entity2,
-            &mut health_query,
-            &collision_damage_query,
-        );
-        try_damage(
-            *entity2,
-            *entity1,
-            &mut health_query,
-            &collision_damage_query,
-        );
-    }
-}
-
-/// Apply the collision damage of one entity to the health of another if they both have the correct components to do so.
+        for (damager, damaged) in [(*entity1, *entity2), (*entity2, *entity1)] {
+            if let Some(damage) =
+                try_damage(damager, damaged, &health_query, &collision_damage_query)
+            {
+                damage_events.send(damage);
+            }
+        }
+    }
+}
+
+/// The damage one entity does to another if they both have the correct components for it.
 fn try_damage(
     damager: Entity,
     damaged: Entity,
-    health_query: &mut Query<&mut Health>,
+    health_query: &Query<(), With<Health>>,
     collision_damage_query: &Query<&CollisionDamage>,
+) -> Option<DamageEvent> {
+    let collision_damage = collision_damage_query.get(damager).ok()?;
+    if !health_query.contains(damaged) {
+        return None;
+    }
+
+    Some(DamageEvent {
+        source: Some(damager),
+        target: damaged,
+        amount: collision_damage.amount,
+        kind: collision_damage.kind,
+    })
+}
+
+/// Make things with health bounce off each other, each knocked back by an amount that depends on how heavy the other
+/// one is.  Mass comes from the size of the collider, so bigger asteroids hit harder and are harder to move.
+/// Projectiles don't bounce, they just push whatever they hit.
+fn exchange_momentum(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut bodies: Query<
+        (
+            &Transform,
+            &Collider,
+            &mut Velocity,
+            &mut AngularVelocity,
+            Option<&CollisionPush>,
+        ),
+        With<Health>,
+    >,
+    faction_query: Query<&Faction>,
+    tuning: Res<CollisionTuning>,
 ) {
-    // Return early if the damager doesn't have a CollisionDamage component.
-    let Ok(collision_damage) = collision_damage_query.get(damager) else {
-        return;
-    };
+    for event in collision_event_reader.read() {
+        let CollisionEvent::Started(entity1, entity2, _) = event else {
+            continue;
+        };
+
+        if let (Ok(faction1), Ok(faction2)) =
+            (faction_query.get(*entity1), faction_query.get(*entity2))
+        {
+            if !tuning.interacts(*faction1, *faction2) {
+                continue;
+            }
+        }
 
-    // Return early if the damaged doesn't have a Health component.
-    let Ok(mut health) = health_query.get_mut(damaged) else {
-        return;
-    };
+        let Ok([first, second]) = bodies.get_many_mut([*entity1, *entity2]) else {
+            continue;
+        };
+        let (transform1, collider1, mut velocity1, mut angular_velocity1, push1) = first;
+        let (transform2, collider2, mut velocity2, mut angular_velocity2, push2) = second;
+
+        // Everything flies around on the XZ plane, so that's the only way anything gets knocked.
+        let flat = Vec3::new(1.0, 0.0, 1.0);
+        let relative_velocity = (velocity1.value - velocity2.value) * flat;
+        let normal = ((transform2.translation - transform1.translation) * flat)
+            .try_normalize()
+            .or_else(|| relative_velocity.try_normalize());
+        let Some(normal) = normal else {
+            continue;
+        };
+        let mass1 = mass(collider1);
+        let mass2 = mass(collider2);
+
+        // Projectiles push things the way they're flying, which still works when they've already flown past.
+        match (push1, push2) {
+            (Some(_), Some(_)) => {}
+            (Some(push), None) => {
+                let direction = (velocity1.value * flat).try_normalize().unwrap_or(normal);
+                velocity2.value += direction * push.momentum / mass2;
+            }
+            (None, Some(push)) => {
+                let direction = (velocity2.value * flat).try_normalize().unwrap_or(-normal);
+                velocity1.value += direction * push.momentum / mass1;
+            }
+            (None, None) => {
+                // Things that are already moving apart have nothing to bounce off.
+                let closing_speed = relative_velocity.dot(normal);
+                if closing_speed <= 0.0 {
+                    continue;
+                }
+
+                let restitution = tuning.restitution.clamp(0.0, 1.0);
+                let impulse = (1.0 + restitution) * closing_speed * mass1 * mass2 / (mass1 + mass2);
+                velocity1.value -= normal * impulse / mass1;
+                velocity2.value += normal * impulse / mass2;
+
+                // Sliding past each other spins them both the same way, the lighter one more.
+                let sideways_speed = normal.cross(relative_velocity).y;
+                angular_velocity1.value.y -= tuning.spin * sideways_speed * mass2 / (mass1 + mass2);
+                angular_velocity2.value.y -= tuning.spin * sideways_speed * mass1 / (mass1 + mass2);
+            }
+        }
+    }
+}
 
-    health.value -= collision_damage.amount;
+/// How heavy something is, which is how much room its collider takes up.  Colliders are kept scaled with their
+/// transforms, so asteroids get lighter as they lose health and shrink.
+fn mass(collider: &Collider) -> f32 {
+    collider.raw.mass_properties(1.0).mass().max(f32::EPSILON)
 }
@@ -1,12 +1,14 @@
 use bevy::prelude::*;
+use bevy_inspector_egui::quick::ResourceInspectorPlugin;
 
-use crate::schedule::InGameSet;
+use crate::director::Director;
 
 pub struct DebugPlugin;
 
 impl Plugin for DebugPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, print_position.after(InGameSet::EntityUpdates));
+        app.add_plugins(ResourceInspectorPlugin::<Director>::default())
+            .add_systems(Update, print_position);
     }
 }
 
@@ -13,16 +13,32 @@ pub struct DespawnPlugin;
 
 impl Plugin for DespawnPlugin {
     fn build(&self, app: &mut App) {
-        app
-            .add_systems(Update, despawn_on_die.in_set(InGameSet::DespawnEntities))
-            .add_systems(Update, update_despawn_timer)
-            .add_systems(
-                OnEnter(GameState::GameOver),
-                remove_with_component::<Health>,
-            );
+        app.configure_sets(
+            FixedUpdate,
+            DeathReaderSet
+                .before(despawn_on_die)
+                .in_set(InGameSet::DespawnEntities),
+        )
+        .add_systems(
+            FixedUpdate,
+            despawn_on_die.in_set(InGameSet::DespawnEntities),
+        )
+        .add_systems(
+            FixedUpdate,
+            update_despawn_timer.in_set(InGameSet::EntityUpdates),
+        )
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            remove_with_component::<Health>,
+        );
     }
 }
 
+/// The systems that read [DieEvent]s and look at the entities that died.  [despawn_on_die] despawns with commands, and
+/// these run before it, so the dead are all still there to look at until the commands are applied.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct DeathReaderSet;
+
 /// With this component added, entities will be recursively despawned when their health component is zero or less.
 #[derive(Debug, Component)]
 pub struct DespawnOnDie;
@@ -35,8 +51,8 @@ fn despawn_on_die(
 ) {
     die_events
         .read()
-        .filter(|DieEvent { entity }| despawn_on_die_query.get(*entity).is_ok())
-        .for_each(|DieEvent { entity }| commands.entity(*entity).despawn_recursive());
+        .filter(|DieEvent { entity, .. }| despawn_on_die_query.get(*entity).is_ok())
+        .for_each(|DieEvent { entity, .. }| commands.entity(*entity).despawn_recursive());
 }
 
 /// Recursively despawn an entity if it has a component of type T attached to it.
@@ -69,7 +85,7 @@ fn update_despawn_timer(
         die_timer.timer.tick(time.delta());
 
         if die_timer.timer.just_finished() {
-            die_events.send(DieEvent { entity });
+            die_events.send(DieEvent::new(entity));
         }
     }
 }
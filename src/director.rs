@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asteroids::{Asteroid, AsteroidTuning},
+    collision::Faction,
+    despawn::DeathReaderSet,
+    health::{DieEvent, Health},
+    movement::MovementSet,
+    schedule::InGameSet,
+    spaceship::Spaceship,
+    state::GameState,
+    wave::Wave,
+};
+
+/// Paces the asteroids in each wave.  The director watches how hard a time the player is having, and sends
+/// asteroids in faster or slower, and tougher or weaker, to keep that close to an intensity curve.
+pub struct DirectorPlugin;
+
+impl Plugin for DirectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DirectorTuning>()
+            .init_resource::<Director>()
+            .register_type::<Director>()
+            .add_systems(
+                OnTransition {
+                    from: GameState::Start,
+                    to: GameState::InGame,
+                },
+                reset_director,
+            )
+            .add_systems(FixedUpdate, count_kills.in_set(DeathReaderSet))
+            .add_systems(
+                FixedUpdate,
+                (read_signals, direct)
+                    .chain()
+                    .before(MovementSet)
+                    .in_set(InGameSet::EntityUpdates),
+            );
+    }
+}
+
+/// Balance numbers for the director, loaded from the tuning file.  See [crate::tuning].  The time between asteroids
+/// starts from [AsteroidTuning::spawn_time_seconds].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DirectorTuning {
+    /// The intensity to aim for, as `(seconds into the wave, intensity)` points from 0.0 to 1.0.  The director
+    /// blends between the points, and starts again from the first once it's past the last.
+    pub intensity_curve: Vec<(f32, f32)>,
+    /// The most asteroids, fragments included, allowed in play at once.
+    pub max_asteroids: u32,
+    /// How much each signal counts towards the intensity.  Low health, recent damage and a crowded play area all
+    /// make things more intense.
+    pub health_weight: f32,
+    pub damage_weight: f32,
+    pub crowd_weight: f32,
+    /// How long after the spaceship is hit before the damage stops counting towards the intensity.
+    pub damage_memory_seconds: f32,
+    /// How many seconds the kill rate is averaged over.
+    pub kill_rate_seconds: f32,
+    /// How strongly the spawn rate reacts to being off the curve.  At 1.0, being a whole intensity below the curve
+    /// halves the time between asteroids.
+    pub spawn_rate_response: f32,
+    /// How much tougher asteroids get while the intensity is below the curve, for each unit it's below by.
+    pub toughness_response: f32,
+    /// How much tougher asteroids get for each asteroid the player is destroying a second.
+    pub toughness_per_kill_rate: f32,
+    pub min_toughness: f32,
+    pub max_toughness: f32,
+}
+
+impl Default for DirectorTuning {
+    fn default() -> Self {
+        Self {
+            intensity_curve: vec![(0.0, 0.2), (30.0, 0.6), (45.0, 0.8), (60.0, 0.3)],
+            max_asteroids: 16,
+            health_weight: 0.4,
+            damage_weight: 0.3,
+            crowd_weight: 0.3,
+            damage_memory_seconds: 5.0,
+            kill_rate_seconds: 10.0,
+            spawn_rate_response: 1.0,
+            toughness_response: 0.5,
+            toughness_per_kill_rate: 0.25,
+            min_toughness: 0.75,
+            max_toughness: 2.0,
+        }
+    }
+}
+
+impl DirectorTuning {
+    /// The intensity to aim for at a point in the wave.
+    pub fn target_intensity(&self, seconds: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.intensity_curve.first(), self.intensity_curve.last())
+        else {
+            return 0.0;
+        };
+
+        let seconds = if last.0 > 0.0 {
+            seconds % last.0
+        } else {
+            seconds
+        };
+        if seconds <= first.0 {
+            return first.1;
+        }
+
+        for window in self.intensity_curve.windows(2) {
+            let [(start, from), (end, to)] = [window[0], window[1]];
+            if seconds <= end {
+                let t = if end > start {
+                    (seconds - start) / (end - start)
+                } else {
+                    1.0
+                };
+                return from + (to - from) * t;
+            }
+        }
+
+        last.1
+    }
+}
+
+/// What the director has seen, and what it's decided.  Shown in the debug overlay.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub struct Director {
+    /// The spaceship's health, from 0.0 to 1.0.  Zero while there's no spaceship.
+    pub player_health: f32,
+    pub asteroids: u32,
+    pub seconds_since_damage: f32,
+    /// How many asteroids the player has been destroying a second, lately.
+    pub kill_rate: f32,
+    /// How intense things are right now, from 0.0 to 1.0.
+    pub intensity: f32,
+    /// How intense the curve says things should be.
+    pub target_intensity: f32,
+    /// How long between asteroids coming in.
+    pub spawn_interval: f32,
+    /// What the wave's asteroid health is multiplied by.
+    pub toughness: f32,
+    wave: u32,
+    seconds_into_wave: f32,
+    /// Counts down to the next asteroid.
+    spawn_cooldown: f32,
+    last_player_health: Option<f32>,
+    kills: u32,
+}
+
+impl Default for Director {
+    fn default() -> Self {
+        Self {
+            player_health: 1.0,
+            asteroids: 0,
+            seconds_since_damage: f32::INFINITY,
+            kill_rate: 0.0,
+            intensity: 0.0,
+            target_intensity: 0.0,
+            spawn_interval: AsteroidTuning::default().spawn_time_seconds,
+            toughness: 1.0,
+            wave: 0,
+            seconds_into_wave: 0.0,
+            spawn_cooldown: AsteroidTuning::default().spawn_time_seconds,
+            last_player_health: None,
+            kills: 0,
+        }
+    }
+}
+
+impl Director {
+    /// Whether another asteroid can come in now.  If it can, the wait for the next one starts.
+    pub fn take_spawn(&mut self, tuning: &DirectorTuning) -> bool {
+        if self.spawn_cooldown > 0.0 || self.asteroids >= tuning.max_asteroids {
+            return false;
+        }
+
+        self.spawn_cooldown = self.spawn_interval;
+        self.asteroids += 1;
+        true
+    }
+}
+
+fn reset_director(mut director: ResMut<Director>, asteroid_tuning: Res<AsteroidTuning>) {
+    *director = Director {
+        spawn_interval: asteroid_tuning.spawn_time_seconds,
+        spawn_cooldown: asteroid_tuning.spawn_time_seconds,
+        ..default()
+    };
+}
+
+/// Count the asteroids the player destroys.
+fn count_kills(
+    mut die_events: EventReader<DieEvent>,
+    asteroids: Query<(), With<Asteroid>>,
+    factions: Query<&Faction>,
+    mut director: ResMut<Director>,
+) {
+    for event in die_events.read() {
+        if asteroids.contains(event.entity) && event.credited_to_player(&factions) {
+            director.kills += 1;
+        }
+    }
+}
+
+/// Keep an eye on the player's health, how crowded the play area is, and how quickly asteroids are being destroyed.
+fn read_signals(
+    mut director: ResMut<Director>,
+    spaceship: Query<&Health, With<Spaceship>>,
+    asteroids: Query<(), With<Asteroid>>,
+    tuning: Res<DirectorTuning>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+
+    let spaceship_health = spaceship.get_single().ok();
+    let health = spaceship_health.map(|health| health.value);
+    if let (Some(health), Some(last_health)) = (health, director.last_player_health) {
+        if health < last_health {
+            director.seconds_since_damage = 0.0;
+        }
+    }
+    director.seconds_since_damage += dt;
+    director.last_player_health = health;
+    director.player_health = spaceship_health.map_or(0.0, |health| health.fraction().min(1.0));
+    director.asteroids = asteroids.iter().count() as u32;
+
+    if dt > 0.0 {
+        let kills_per_second = director.kills as f32 / dt;
+        let blend = (dt / tuning.kill_rate_seconds.max(dt)).min(1.0);
+        director.kill_rate += (kills_per_second - director.kill_rate) * blend;
+    }
+    director.kills = 0;
+}
+
+/// Work out how intense things are, and steer the spawn rate and toughness towards the curve.
+fn direct(
+    mut director: ResMut<Director>,
+    wave: Res<Wave>,
+    tuning: Res<DirectorTuning>,
+    asteroid_tuning: Res<AsteroidTuning>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+
+    if director.wave != wave.number {
+        director.wave = wave.number;
+        director.seconds_into_wave = 0.0;
+    }
+    // The curve only runs while the wave is sending asteroids in.
+    if wave.is_spawning() {
+        director.seconds_into_wave += dt;
+    }
+
+    let recent_damage = if tuning.damage_memory_seconds > 0.0 {
+        (1.0 - director.seconds_since_damage / tuning.damage_memory_seconds).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let crowd = if tuning.max_asteroids > 0 {
+        (director.asteroids as f32 / tuning.max_asteroids as f32).min(1.0)
+    } else {
+        1.0
+    };
+    director.intensity = (tuning.health_weight * (1.0 - director.player_health)
+        + tuning.damage_weight * recent_damage
+        + tuning.crowd_weight * crowd)
+        .clamp(0.0, 1.0);
+    director.target_intensity = tuning.target_intensity(director.seconds_into_wave);
+
+    // Decisions.  Below the curve, asteroids come in quicker and tougher, and above it they ease off.
+    let shortfall = director.target_intensity - director.intensity;
+    director.spawn_interval =
+        asteroid_tuning.spawn_time_seconds * 2.0_f32.powf(-shortfall * tuning.spawn_rate_response);
+    director.toughness = ((1.0 + shortfall * tuning.toughness_response)
+        * (1.0 + director.kill_rate * tuning.toughness_per_kill_rate))
+        .clamp(
+            tuning.min_toughness,
+            tuning.max_toughness.max(tuning.min_toughness),
+        );
+
+    if wave.is_spawning() {
+        let spawn_interval = director.spawn_interval;
+        director.spawn_cooldown = director.spawn_cooldown.min(spawn_interval) - dt;
+    }
+}
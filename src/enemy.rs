@@ -0,0 +1,362 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asset_loader::SceneAssets,
+    asteroids::random_2d_unit_vector,
+    collision::{CollisionDamage, Faction},
+    despawn::DespawnOnDie,
+    health::{Armor, DamageKind, Health},
+    hyperspace::travel_through_hyperspace,
+    movement::{
+        Acceleration, AngularVelocity, ConfinedToPlayArea, MovementSet, MovementTuning,
+        MovingObjectBundle, Velocity,
+    },
+    rng::GameRng,
+    schedule::InGameSet,
+    spaceship::Spaceship,
+    state::GameState,
+    wave::Wave,
+    weapon::{ProjectileBundle, ProjectileModel, ProjectileTemplate},
+};
+
+/// How flat a saucer is, as a fraction of its radius.
+const SAUCER_FLATNESS: f32 = 0.35;
+
+/// Adds the hostile saucers, which fly in now and then to hunt the spaceship.
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaucerTuning>()
+            .init_resource::<SaucerSpawnTimer>()
+            .add_systems(
+                OnTransition {
+                    from: GameState::Start,
+                    to: GameState::InGame,
+                },
+                reset_saucer_spawn_timer,
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    count_down_to_saucers,
+                    spawn_saucers,
+                    steer_saucers,
+                    saucers_fire,
+                )
+                    .chain()
+                    // Hyperspace jumps pick where to come out with the game's RNG, so the saucers always take their
+                    // turn after them.
+                    .after(travel_through_hyperspace)
+                    .before(MovementSet)
+                    .in_set(InGameSet::EntityUpdates),
+            );
+    }
+}
+
+/// Balance numbers for the saucers, loaded from the tuning file.  See [crate::tuning].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SaucerTuning {
+    /// How long after the last saucer has gone before another flies in.
+    pub spawn_seconds: f32,
+    /// The chance of a saucer being a small one, from 0.0 to 1.0.
+    pub small_chance: f64,
+    /// How often saucers change direction.
+    pub turn_seconds: f32,
+    pub large: SaucerStats,
+    pub small: SaucerStats,
+    pub projectile: ProjectileTemplate,
+}
+
+impl Default for SaucerTuning {
+    fn default() -> Self {
+        Self {
+            spawn_seconds: 20.0,
+            small_chance: 0.3,
+            turn_seconds: 1.5,
+            large: SaucerStats {
+                health: 20.0,
+                speed: 8.0,
+                radius: 4.0,
+                collision_damage: 30.0,
+                fire_seconds: 1.5,
+                projectile_damage: 10.0,
+                points: 20.0,
+                aimed: false,
+                aim_error: 0.0,
+                // The big ones are plated against beams.
+                armor: Armor {
+                    flat: 0.0,
+                    resistances: vec![(DamageKind::Energy, 0.5)],
+                },
+            },
+            small: SaucerStats {
+                health: 8.0,
+                speed: 14.0,
+                radius: 2.5,
+                collision_damage: 20.0,
+                fire_seconds: 1.0,
+                projectile_damage: 10.0,
+                points: 50.0,
+                aimed: true,
+                aim_error: 10.0,
+                armor: Armor::default(),
+            },
+            projectile: ProjectileTemplate {
+                model: ProjectileModel::Missile,
+                speed: 35.0,
+                lifespan_millis: 2000,
+                health: 1.0,
+                radius: 0.5,
+                length: 0.0,
+                forward_offset: 1.0,
+                homing_turn_rate: 0.0,
+                push: 200.0,
+                damage_kind: DamageKind::Energy,
+            },
+        }
+    }
+}
+
+/// How one size of saucer flies and fights.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SaucerStats {
+    pub health: f32,
+    pub speed: f32,
+    pub radius: f32,
+    pub collision_damage: f32,
+    pub fire_seconds: f32,
+    pub projectile_damage: f32,
+    /// The bonus the player gets for destroying it.
+    pub points: f32,
+    /// Aimed shots go at the spaceship, and the rest go anywhere.
+    pub aimed: bool,
+    /// How far off, in degrees either way, an aimed shot can be.
+    pub aim_error: f32,
+    pub armor: Armor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaucerSize {
+    Large,
+    Small,
+}
+
+impl SaucerTuning {
+    pub fn stats(&self, size: SaucerSize) -> &SaucerStats {
+        match size {
+            SaucerSize::Large => &self.large,
+            SaucerSize::Small => &self.small,
+        }
+    }
+}
+
+/// A hostile saucer.
+#[derive(Component, Debug)]
+pub struct Saucer {
+    pub size: SaucerSize,
+    /// The bonus the player gets for destroying it.
+    pub points: f32,
+    turn_timer: Timer,
+    fire_timer: Timer,
+}
+
+/// Marker component for projectiles fired by saucers.
+#[derive(Component, Debug)]
+pub struct SaucerMissile;
+
+/// Counts down to the next saucer while there isn't one about.
+#[derive(Resource, Debug, Default)]
+struct SaucerSpawnTimer {
+    timer: Timer,
+}
+
+#[derive(Bundle)]
+pub struct SaucerBundle {
+    pub moving_object_bundle: MovingObjectBundle,
+    pub saucer: Saucer,
+    pub health: Health,
+    pub armor: Armor,
+    pub collision_damage: CollisionDamage,
+    pub active_events: ActiveEvents,
+    pub despawn_on_die: DespawnOnDie,
+    pub confined_to_play_area: ConfinedToPlayArea,
+}
+
+impl SaucerBundle {
+    pub fn new(size: SaucerSize, translation: Vec3, velocity: Vec3, tuning: &SaucerTuning) -> Self {
+        let stats = tuning.stats(size);
+
+        let moving_object_bundle = MovingObjectBundle::new(
+            SceneBundle {
+                transform: Transform::from_translation(translation),
+                ..default()
+            },
+            Collider::cylinder(stats.radius * SAUCER_FLATNESS, stats.radius),
+            Faction::Enemy,
+            velocity,
+            Acceleration::new(Vec3::ZERO),
+            AngularVelocity::new(Vec3::new(0.0, 2.0, 0.0)),
+        );
+
+        SaucerBundle {
+            moving_object_bundle,
+            saucer: Saucer {
+                size,
+                points: stats.points,
+                turn_timer: Timer::from_seconds(tuning.turn_seconds.max(0.0), TimerMode::Repeating),
+                fire_timer: Timer::from_seconds(stats.fire_seconds.max(0.0), TimerMode::Repeating),
+            },
+            health: Health::new(stats.health),
+            armor: stats.armor.clone(),
+            collision_damage: CollisionDamage::new(stats.collision_damage),
+            active_events: ActiveEvents::COLLISION_EVENTS,
+            despawn_on_die: DespawnOnDie,
+            confined_to_play_area: ConfinedToPlayArea,
+        }
+    }
+}
+
+fn reset_saucer_spawn_timer(mut spawn_timer: ResMut<SaucerSpawnTimer>) {
+    spawn_timer.timer.reset();
+}
+
+/// The wait for the next saucer only counts down while there isn't one about, and while a wave is on.  It follows
+/// the tuning as it changes, so a shorter wait can bring the next saucer in straight away.
+fn count_down_to_saucers(
+    saucers: Query<(), With<Saucer>>,
+    mut spawn_timer: ResMut<SaucerSpawnTimer>,
+    wave: Res<Wave>,
+    tuning: Res<SaucerTuning>,
+    time: Res<Time>,
+) {
+    if saucers.is_empty() && !wave.is_announcing() {
+        spawn_timer
+            .timer
+            .set_duration(Duration::from_secs_f32(tuning.spawn_seconds.max(0.0)));
+        spawn_timer.timer.tick(time.delta());
+    }
+}
+
+/// Send a saucer in from the edge of the play area once the wait for it is over.
+fn spawn_saucers(
+    mut commands: Commands,
+    mut spawn_timer: ResMut<SaucerSpawnTimer>,
+    assets: Res<SceneAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<SaucerTuning>,
+    movement: Res<MovementTuning>,
+) {
+    if !spawn_timer.timer.finished() {
+        return;
+    }
+    spawn_timer.timer.reset();
+
+    let rng = rng.as_mut();
+    let size = if rng.gen_bool(tuning.small_chance.clamp(0.0, 1.0)) {
+        SaucerSize::Small
+    } else {
+        SaucerSize::Large
+    };
+    let stats = tuning.stats(size);
+
+    // Come in just inside the edge, heading somewhere across the middle.
+    let translation = random_2d_unit_vector(rng) * (movement.world_size - stats.radius * 2.0);
+    let velocity = (random_2d_unit_vector(rng) * (movement.world_size * 0.5) - translation)
+        .normalize_or_zero()
+        * stats.speed;
+
+    commands
+        .spawn(SaucerBundle::new(size, translation, velocity, &tuning))
+        .with_children(|builder| {
+            builder.spawn(PbrBundle {
+                mesh: assets.shield_bubble.clone(),
+                material: assets.saucer_material.clone(),
+                transform: Transform::from_scale(Vec3::new(
+                    stats.radius,
+                    stats.radius * SAUCER_FLATNESS,
+                    stats.radius,
+                )),
+                ..default()
+            });
+        });
+}
+
+/// Saucers lurch off in a new direction every so often.
+fn steer_saucers(
+    mut saucers: Query<(&mut Saucer, &mut Velocity)>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<SaucerTuning>,
+    time: Res<Time>,
+) {
+    for (mut saucer, mut velocity) in saucers.iter_mut() {
+        if !saucer.turn_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let speed = tuning.stats(saucer.size).speed;
+        velocity.value = random_2d_unit_vector(rng.as_mut()) * speed;
+    }
+}
+
+/// Small saucers shoot at the spaceship, and large ones shoot anywhere.
+fn saucers_fire(
+    mut commands: Commands,
+    mut saucers: Query<(&mut Saucer, &Transform)>,
+    spaceship: Query<&Transform, With<Spaceship>>,
+    assets: Res<SceneAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<SaucerTuning>,
+    time: Res<Time>,
+) {
+    let rng = rng.as_mut();
+    let target = spaceship
+        .get_single()
+        .ok()
+        .map(|transform| transform.translation);
+
+    for (mut saucer, transform) in saucers.iter_mut() {
+        if !saucer.fire_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let stats = tuning.stats(saucer.size);
+        let direction = match target {
+            Some(target) if stats.aimed => {
+                let error = stats.aim_error.to_radians();
+                let error = if error > 0.0 {
+                    rng.gen_range(-error..error)
+                } else {
+                    0.0
+                };
+                Quat::from_rotation_y(error) * (target - transform.translation).normalize_or_zero()
+            }
+            _ => random_2d_unit_vector(rng),
+        };
+        let Some(direction) = direction.try_normalize() else {
+            continue;
+        };
+
+        // Start the shot clear of the saucer so it doesn't hit it.
+        let offset = stats.radius + tuning.projectile.radius + tuning.projectile.forward_offset;
+        let projectile = Transform::from_translation(transform.translation + direction * offset)
+            .with_rotation(Quat::from_rotation_arc(Vec3::Z, direction));
+
+        commands.spawn((
+            ProjectileBundle::new(
+                assets.missiles.clone(),
+                projectile,
+                &tuning.projectile,
+                Faction::EnemyProjectile,
+                stats.projectile_damage,
+            ),
+            SaucerMissile,
+        ));
+    }
+}
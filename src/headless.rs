@@ -0,0 +1,267 @@
+use std::{fmt, path::PathBuf};
+
+use bevy::app::{PluginGroupBuilder, PluginsState};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::{
+    asset_loader::{AssetLoaderPlugin, SceneAssets},
+    camera::{camera_transform, CameraPlugin},
+    health::Health,
+    input::InputActionPlugin,
+    replay::{Replay, ReplayPlugin},
+    rng::{GameSeed, RngPlugin},
+    schedule::set_tick_rate,
+    scoreboard::Scoreboard,
+    spaceship::{Lives, Spaceship},
+    state::GameState,
+    tuning::{TuningPlugin, TuningSource},
+    ui::UiPlugin,
+    wave::Wave,
+    AsteroidsPlugins,
+};
+
+/// How many updates past the end of a replay to wait for the game to end, before giving up on it.  Starting the
+/// game and ending it take an update each.
+const REPLAY_END_MARGIN: u32 = 60;
+
+/// Swaps the rendered parts of the game for stand-ins so it can run without a window or GPU.  Each update advances
+/// time by exactly one fixed tick.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        let timestep = app.world.resource::<Time<Fixed>>().timestep();
+
+        app.init_resource::<SceneAssets>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(timestep))
+            .add_systems(Startup, (spawn_stand_in_camera, start_game));
+    }
+}
+
+/// An entity taking the place of the camera when running headless.
+#[derive(Component, Debug)]
+pub struct HeadlessCamera;
+
+fn spawn_stand_in_camera(mut commands: Commands) {
+    commands.spawn((
+        TransformBundle::from_transform(camera_transform()),
+        HeadlessCamera,
+    ));
+}
+
+/// Skip the start screen, there's nobody there to press start.
+fn start_game(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::InGame);
+}
+
+/// Build an app that runs the game logic with no window or renderer.  See [RngPlugin] for the seed.  The tuning file
+/// is never watched, since there's nobody there to edit it.
+pub fn headless_app(seed: Option<u64>, tuning: TuningSource) -> App {
+    build_headless_app(
+        AsteroidsPlugins
+            .build()
+            .set(RngPlugin { seed })
+            .set(TuningPlugin { source: tuning }),
+    )
+}
+
+/// Build an app like [headless_app] that records each game into a replay file.
+pub fn recording_app(seed: Option<u64>, tuning: TuningSource, path: PathBuf) -> App {
+    build_headless_app(
+        AsteroidsPlugins
+            .build()
+            .set(RngPlugin { seed })
+            .set(TuningPlugin { source: tuning })
+            .set(ReplayPlugin {
+                record_path: Some(path),
+                playback: None,
+            }),
+    )
+}
+
+/// Build an app that plays a replay back with no window or renderer.
+pub fn replay_app(replay: Replay) -> App {
+    build_headless_app(
+        AsteroidsPlugins
+            .build()
+            // The replay brings its own tuning.
+            .set(TuningPlugin {
+                source: TuningSource::Defaults,
+            })
+            .set(ReplayPlugin::playback(replay)),
+    )
+}
+
+/// Step a headless app this many times a second instead, still one tick per update.  Call it before the first update.
+pub fn set_headless_tick_rate(app: &mut App, tick_rate: f64) {
+    set_tick_rate(&mut app.world, tick_rate);
+    let timestep = app.world.resource::<Time<Fixed>>().timestep();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+}
+
+fn build_headless_app(game_plugins: PluginGroupBuilder) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins((
+            TransformPlugin,
+            HierarchyPlugin,
+            InputPlugin,
+            // Nothing is loaded from disk, so there's nothing to watch.
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..default()
+            },
+            ScenePlugin,
+        ))
+        // Rapier looks for mesh assets when building colliders.
+        .init_asset::<Mesh>()
+        .add_plugins(
+            game_plugins
+                .set(InputActionPlugin {
+                    bindings_path: None,
+                })
+                .disable::<AssetLoaderPlugin>()
+                .disable::<CameraPlugin>()
+                .disable::<UiPlugin>(),
+        )
+        .add_plugins(HeadlessPlugin);
+
+    // App::run would normally finish setting up the plugins for us.
+    while app.plugins_state() == PluginsState::Adding {
+        bevy::tasks::tick_global_task_pools_on_main_thread();
+    }
+    app.finish();
+    app.cleanup();
+
+    app
+}
+
+/// The state of the game at the end of a headless run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessReport {
+    /// How many ticks were simulated before the run ended.
+    pub ticks: u32,
+    /// The seed the game was played with.
+    pub seed: u64,
+    pub score: f32,
+    /// The spaceship's health, or [None] if it was destroyed.
+    pub spaceship_health: Option<f32>,
+    /// How many spaceships were left, counting the one in play.
+    pub lives: u32,
+    /// The wave that was being played.
+    pub wave: u32,
+}
+
+impl fmt::Display for HeadlessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Ticks: {}", self.ticks)?;
+        writeln!(f, "Seed: {}", self.seed)?;
+        writeln!(f, "Score: {:.1}", self.score)?;
+        writeln!(f, "Wave: {}", self.wave)?;
+        writeln!(f, "Lives: {}", self.lives)?;
+        match self.spaceship_health {
+            Some(health) => write!(f, "Health: {health:.1}"),
+            None => write!(f, "Health: destroyed"),
+        }
+    }
+}
+
+/// Run a game headless for up to the given number of ticks, stopping early if the game ends.
+pub fn run_headless(ticks: u32, seed: Option<u64>) -> HeadlessReport {
+    let mut app = headless_app(seed, TuningSource::File);
+
+    let mut ticks_run = 0;
+    while ticks_run < ticks {
+        app.update();
+        ticks_run += 1;
+
+        if *app.world.resource::<State<GameState>>().get() != GameState::InGame {
+            break;
+        }
+    }
+
+    report(&mut app, ticks_run)
+}
+
+/// The result of playing a replay back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayVerification {
+    /// How many ticks the replay lasts.
+    pub ticks: u32,
+    /// Whether the game ended once the replay ran out.  A game that keeps going has gone wrong somewhere.
+    pub ended: bool,
+    pub recorded_score: f32,
+    pub score: f32,
+}
+
+impl ReplayVerification {
+    /// Whether the replay played out to the end, and to the score it was recorded with.
+    pub fn matches(&self) -> bool {
+        self.ended && self.score == self.recorded_score
+    }
+}
+
+impl fmt::Display for ReplayVerification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Ticks: {}", self.ticks)?;
+        if !self.ended {
+            writeln!(f, "The game didn't end with the replay")?;
+        }
+        writeln!(f, "Recorded score: {:.1}", self.recorded_score)?;
+        writeln!(f, "Score: {:.1}", self.score)?;
+        if self.matches() {
+            write!(f, "The replay matches")
+        } else {
+            write!(f, "The replay DOESN'T match")
+        }
+    }
+}
+
+/// Play a replay through headless, and check that it ends with the score it was recorded with.
+pub fn verify_replay(replay: Replay) -> ReplayVerification {
+    let ticks = replay.inputs.ticks();
+    let recorded_score = replay.final_score;
+    let mut app = replay_app(replay);
+
+    // The replay ends the game once it runs out of ticks.
+    let mut ended = false;
+    for _ in 0..ticks.saturating_add(REPLAY_END_MARGIN) {
+        app.update();
+        if *app.world.resource::<State<GameState>>().get() != GameState::InGame {
+            ended = true;
+            break;
+        }
+    }
+
+    ReplayVerification {
+        ticks,
+        ended,
+        recorded_score,
+        score: app.world.resource::<Scoreboard>().score,
+    }
+}
+
+fn report(app: &mut App, ticks: u32) -> HeadlessReport {
+    let seed = app.world.resource::<GameSeed>().0;
+    let score = app.world.resource::<Scoreboard>().score;
+    let spaceship_health = app
+        .world
+        .query_filtered::<&Health, With<Spaceship>>()
+        .get_single(&app.world)
+        .ok()
+        .map(|health| health.value);
+    let lives = app.world.resource::<Lives>().remaining;
+    let wave = app.world.resource::<Wave>().number;
+
+    HeadlessReport {
+        ticks,
+        seed,
+        score,
+        spaceship_health,
+        lives,
+        wave,
+    }
+}
@@ -1,14 +1,32 @@
 use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
 
-use crate::schedule::InGameSet;
+use crate::{collision::Faction, schedule::InGameSet, shield::Shield, state::playing};
 
-/// Handles the health of entities.
+/// Handles the health of entities, and the damage and healing done to it.
 pub struct HealthPlugin;
 
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<DieEvent>()
-            .add_systems(Update, send_die_event.in_set(InGameSet::EntityUpdates));
+        app.add_event::<DamageEvent>()
+            .add_event::<ResistedEvent>()
+            .add_event::<HealEvent>()
+            .add_event::<DieEvent>()
+            .init_resource::<KillingBlows>()
+            .add_systems(
+                FixedUpdate,
+                // Damage is applied and deaths are checked once everything that can deal damage this tick has run.
+                // Healing comes after damage, so nothing is saved from a killing blow by being healed the same tick.
+                (apply_damage, apply_healing, send_die_event)
+                    .chain()
+                    .after(InGameSet::CollisionDetection)
+                    .run_if(playing),
+            )
+            .add_systems(
+                FixedUpdate,
+                (wear_off_invulnerability, regenerate).in_set(InGameSet::EntityUpdates),
+            );
     }
 }
 
@@ -16,11 +34,34 @@ impl Plugin for HealthPlugin {
 #[derive(Component, Debug)]
 pub struct Health {
     pub value: f32,
+    /// How much health the entity has when it's fully healed.  Only healing that allows overhealing takes it over
+    /// this, see [HealEvent].
+    pub max: f32,
 }
 
 impl Health {
+    /// Full health.
     pub fn new(value: f32) -> Self {
-        Health { value }
+        Health { value, max: value }
+    }
+
+    /// How much of its maximum health the entity has left, from 0.0 up.  It's over 1.0 while overhealed.
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            self.value.max(0.0) / self.max
+        } else {
+            0.0
+        }
+    }
+
+    /// Heal by an amount, up to maximum health plus however much overhealing is allowed.  Health that's already over
+    /// that isn't taken away, and anything dead stays dead.
+    pub fn heal(&mut self, amount: f32, overheal: f32) {
+        if self.value <= 0.0 || amount <= 0.0 {
+            return;
+        }
+        let healed = (self.value + amount).min(self.max + overheal.max(0.0));
+        self.value = self.value.max(healed);
     }
 }
 
@@ -36,20 +77,276 @@ impl AsRef<f32> for Health {
     }
 }
 
-/// Event that is sent when an entity's health drops to or below zero.
-#[derive(Debug, Event)]
+/// Heals an entity back up to its maximum health, once it's gone a while without taking damage.  It never overheals.
+#[derive(Component, Debug)]
+pub struct Regeneration {
+    pub per_second: f32,
+    /// Starts over whenever the entity's health takes damage, and regeneration only happens once it's finished.
+    pub delay: Timer,
+}
+
+impl Regeneration {
+    pub fn new(per_second: f32, delay_seconds: f32) -> Self {
+        Regeneration {
+            per_second,
+            delay: Timer::from_seconds(delay_seconds.max(0.0), TimerMode::Once),
+        }
+    }
+}
+
+/// Keeps an entity from taking damage until the timer runs out, after which the component is removed.
+#[derive(Component, Debug)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+impl Invulnerable {
+    pub fn from_seconds(seconds: f32) -> Self {
+        Invulnerable {
+            timer: Timer::from_seconds(seconds.max(0.0), TimerMode::Once),
+        }
+    }
+}
+
+/// What sort of damage something deals, which [Armor] can resist some kinds of better than others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum DamageKind {
+    /// Solid shots.
+    Kinetic,
+    /// Shots that blow up.
+    Explosive,
+    /// Beams and blasts of energy.
+    Energy,
+    /// Running into something.
+    Collision,
+    /// A hyperspace jump going wrong.
+    Malfunction,
+}
+
+impl DamageKind {
+    /// The name shown to the player.
+    pub fn name(self) -> &'static str {
+        match self {
+            DamageKind::Kinetic => "Kinetic",
+            DamageKind::Explosive => "Explosive",
+            DamageKind::Energy => "Energy",
+            DamageKind::Collision => "Collision",
+            DamageKind::Malfunction => "Malfunction",
+        }
+    }
+}
+
+/// How well an entity stands up to each kind of damage.  Every hit that would reduce its [Health] goes through it
+/// first.
+#[derive(Component, Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Armor {
+    /// Taken off every hit after resistances, so that small enough hits do nothing at all.
+    pub flat: f32,
+    /// The share of each kind of damage that's shrugged off, up to 1.0.  Below zero is a weakness, taking more
+    /// damage than was dealt.  Kinds that aren't listed aren't resisted.
+    pub resistances: Vec<(DamageKind, f32)>,
+}
+
+impl Armor {
+    pub fn resistance(&self, kind: DamageKind) -> f32 {
+        self.resistances
+            .iter()
+            .find(|(resisted, _)| *resisted == kind)
+            .map_or(0.0, |(_, resistance)| resistance.min(1.0))
+    }
+
+    /// How much of some damage gets through.
+    pub fn reduce(&self, amount: f32, kind: DamageKind) -> f32 {
+        (amount * (1.0 - self.resistance(kind)) - self.flat.max(0.0)).max(0.0)
+    }
+}
+
+/// Event that is sent to deal damage to an entity.  Whatever deals damage sends one of these rather than changing
+/// [Health] itself, so that shields and invulnerability get their say, and deaths know who to blame.
+#[derive(Debug, Clone, Event)]
+pub struct DamageEvent {
+    /// Whatever dealt the damage, if anything did.
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: f32,
+    pub kind: DamageKind,
+}
+
+/// Event that is sent when [Armor] takes some of the sting out of a hit, so the player can be told their shots
+/// aren't doing much.
+#[derive(Debug, Clone, Event)]
+pub struct ResistedEvent {
+    /// Whatever dealt the damage, if anything did.
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub kind: DamageKind,
+    /// How much of the damage was shrugged off.
+    pub amount: f32,
+}
+
+impl ResistedEvent {
+    /// Whether it was the player's damage that was resisted.  See [DieEvent::credited_to_player].
+    pub fn credited_to_player(&self, factions: &Query<&Faction>) -> bool {
+        credited_to_player(self.source, factions)
+    }
+}
+
+/// Event that is sent to heal an entity.  See [Health::heal] for how far it goes.
+#[derive(Debug, Clone, Event)]
+pub struct HealEvent {
+    pub target: Entity,
+    pub amount: f32,
+    /// How far over its maximum health this can take the entity.  Zero stops at maximum health.
+    pub overheal: f32,
+}
+
+/// Event that is sent when an entity's health drops to or below zero, or its time runs out.
+#[derive(Debug, Clone, Event)]
 pub struct DieEvent {
     pub entity: Entity,
+    /// Whatever dealt the killing blow, if anything did.
+    pub killer: Option<Entity>,
+    /// What the killing blow was.  [None] for entities that simply ran out of time or health.
+    pub cause: Option<DamageKind>,
+}
+
+impl DieEvent {
+    /// A death nothing in particular was to blame for.
+    pub fn new(entity: Entity) -> Self {
+        DieEvent {
+            entity,
+            killer: None,
+            cause: None,
+        }
+    }
+
+    /// Whether the player gets the credit for the death.  They do unless the killer is known to be on another side.
+    pub fn credited_to_player(&self, factions: &Query<&Faction>) -> bool {
+        credited_to_player(self.killer, factions)
+    }
+}
+
+/// Whether something done by an entity counts as the player's doing, which it does unless the entity is known to
+/// be on another side.
+fn credited_to_player(entity: Option<Entity>, factions: &Query<&Faction>) -> bool {
+    entity
+        .and_then(|entity| factions.get(entity).ok())
+        .is_none_or(|faction| faction.is_players())
+}
+
+/// The damage that took each entity's health to zero or below this tick.
+#[derive(Resource, Debug, Default)]
+struct KillingBlows(HashMap<Entity, (Option<Entity>, DamageKind)>);
+
+/// Apply this tick's damage.  A raised [Shield] takes the damage first, then [Armor] gets its say on what's left,
+/// and [Invulnerable] entities don't take any.
+fn apply_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    mut resisted_events: EventWriter<ResistedEvent>,
+    mut query: Query<(
+        &mut Health,
+        Option<&mut Shield>,
+        Option<&Armor>,
+        Option<&mut Regeneration>,
+        Has<Invulnerable>,
+    )>,
+    mut killing_blows: ResMut<KillingBlows>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut health, shield, armor, regeneration, invulnerable)) =
+            query.get_mut(event.target)
+        else {
+            continue;
+        };
+        if invulnerable {
+            continue;
+        }
+
+        let mut damage = match shield {
+            Some(mut shield) => shield.absorb(event.amount),
+            None => event.amount,
+        };
+        if let Some(armor) = armor {
+            let reduced = armor.reduce(damage, event.kind);
+            if reduced < damage {
+                resisted_events.send(ResistedEvent {
+                    source: event.source,
+                    target: event.target,
+                    kind: event.kind,
+                    amount: damage - reduced,
+                });
+            }
+            damage = reduced;
+        }
+        if damage <= 0.0 {
+            continue;
+        }
+
+        let was_alive = health.value > 0.0;
+        health.value -= damage;
+        if was_alive && health.value <= 0.0 {
+            killing_blows
+                .0
+                .insert(event.target, (event.source, event.kind));
+        }
+        if let Some(mut regeneration) = regeneration {
+            regeneration.delay.reset();
+        }
+    }
 }
 
-/// Send a [DieEvent] event when an entity's health drops to or below zero.
+/// Apply this tick's healing.
+fn apply_healing(mut heal_events: EventReader<HealEvent>, mut query: Query<&mut Health>) {
+    for event in heal_events.read() {
+        if let Ok(mut health) = query.get_mut(event.target) {
+            health.heal(event.amount, event.overheal);
+        }
+    }
+}
+
+/// Send a [DieEvent] event when an entity's health drops to or below zero, blaming whatever dealt the killing blow.
 fn send_die_event(
     mut event_writer: EventWriter<DieEvent>,
     query: Query<(Entity, &Health), Changed<Health>>,
+    mut killing_blows: ResMut<KillingBlows>,
 ) {
     for (entity, health) in query.iter() {
         if health.value <= 0.0 {
-            event_writer.send(DieEvent { entity });
+            let (killer, cause) = match killing_blows.0.get(&entity) {
+                Some((killer, cause)) => (*killer, Some(*cause)),
+                None => (None, None),
+            };
+            event_writer.send(DieEvent {
+                entity,
+                killer,
+                cause,
+            });
+        }
+    }
+    killing_blows.0.clear();
+}
+
+fn wear_off_invulnerability(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+    time: Res<Time>,
+) {
+    for (entity, mut invulnerable) in query.iter_mut() {
+        if invulnerable.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+/// Heal entities that have gone long enough without taking damage, up to their maximum health.
+fn regenerate(mut query: Query<(&mut Health, &mut Regeneration)>, time: Res<Time>) {
+    for (mut health, mut regeneration) in query.iter_mut() {
+        if !regeneration.delay.tick(time.delta()).finished() {
+            continue;
+        }
+        if health.value > 0.0 && health.value < health.max {
+            health.heal(regeneration.per_second * time.delta_seconds(), 0.0);
         }
     }
 }
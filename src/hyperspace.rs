@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asteroids::{random_2d_unit_vector, Asteroid},
+    health::{DamageEvent, DamageKind, Health},
+    movement::{MovementSet, MovementTuning, TransformInterpolation, Velocity},
+    rng::GameRng,
+    schedule::InGameSet,
+};
+
+/// How many random points are tried when looking for somewhere safe to come out of hyperspace.
+const SAFE_POINT_ATTEMPTS: u32 = 20;
+
+/// Recharges hyperspace drives, and brings entities back out of hyperspace.
+pub struct HyperspacePlugin;
+
+impl Plugin for HyperspacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HyperspaceTuning>().add_systems(
+            FixedUpdate,
+            (recharge_hyperspace_drives, travel_through_hyperspace)
+                .chain()
+                .before(MovementSet)
+                .in_set(InGameSet::EntityUpdates),
+        );
+    }
+}
+
+/// Balance numbers for hyperspace jumps, loaded from the tuning file.  See [crate::tuning].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HyperspaceTuning {
+    pub cooldown_seconds: f32,
+    /// How long a jump takes, while the entity is gone.
+    pub jump_seconds: f32,
+    /// How long after coming back before the entity can be controlled again.
+    pub reentry_seconds: f32,
+    /// The chance of a jump going wrong and damaging the entity, from 0.0 to 1.0.
+    pub malfunction_chance: f64,
+    pub malfunction_damage: f32,
+    /// How far from every asteroid a point has to be to be safe to come out at.
+    pub safe_radius: f32,
+}
+
+impl Default for HyperspaceTuning {
+    fn default() -> Self {
+        Self {
+            cooldown_seconds: 8.0,
+            jump_seconds: 0.5,
+            reentry_seconds: 0.75,
+            malfunction_chance: 0.1,
+            malfunction_damage: 25.0,
+            safe_radius: 15.0,
+        }
+    }
+}
+
+/// Lets an entity jump through hyperspace, once it has recharged from the last jump.
+#[derive(Component, Debug)]
+pub struct HyperspaceDrive {
+    cooldown: Timer,
+}
+
+impl HyperspaceDrive {
+    /// A drive that's ready to jump straight away.
+    pub fn charged() -> Self {
+        let mut cooldown = Timer::new(Duration::ZERO, TimerMode::Once);
+        cooldown.tick(Duration::ZERO);
+        Self { cooldown }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+
+    /// How many seconds are left until the drive can jump again.
+    pub fn remaining_secs(&self) -> f32 {
+        self.cooldown.remaining_secs()
+    }
+}
+
+/// Where an entity is in its jump through hyperspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HyperspacePhase {
+    /// The entity is gone, and can't be seen or hit.
+    Away,
+    /// The entity is back, but can't do anything yet.
+    ReEntering,
+}
+
+/// Added to an entity for as long as it's jumping through hyperspace.
+#[derive(Component, Debug)]
+pub struct HyperspaceJump {
+    phase: HyperspacePhase,
+    timer: Timer,
+}
+
+impl HyperspaceJump {
+    pub fn phase(&self) -> HyperspacePhase {
+        self.phase
+    }
+}
+
+/// Send an entity into hyperspace if its drive is ready.  It stops dead and disappears, and comes back out somewhere
+/// away from the asteroids.  Returns whether it jumped.
+pub fn jump_to_hyperspace(
+    commands: &mut Commands,
+    entity: Entity,
+    drive: &mut HyperspaceDrive,
+    velocity: &mut Velocity,
+    tuning: &HyperspaceTuning,
+) -> bool {
+    if !drive.is_ready() {
+        return false;
+    }
+
+    drive.cooldown = Timer::from_seconds(tuning.cooldown_seconds.max(0.0), TimerMode::Once);
+    velocity.value = Vec3::ZERO;
+    commands.entity(entity).insert((
+        HyperspaceJump {
+            phase: HyperspacePhase::Away,
+            timer: Timer::from_seconds(tuning.jump_seconds.max(0.0), TimerMode::Once),
+        },
+        ColliderDisabled,
+    ));
+
+    true
+}
+
+fn recharge_hyperspace_drives(mut query: Query<&mut HyperspaceDrive>, time: Res<Time>) {
+    for mut drive in query.iter_mut() {
+        drive.cooldown.tick(time.delta());
+    }
+}
+
+/// Bring entities back out of hyperspace at a safe point once their jump is over, with a chance of the jump going
+/// wrong, then give them back control once they've re-entered.
+pub(crate) fn travel_through_hyperspace(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut HyperspaceJump,
+        &mut Transform,
+        Option<&mut TransformInterpolation>,
+        Has<Health>,
+    )>,
+    asteroids: Query<&Transform, (With<Asteroid>, Without<HyperspaceJump>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut rng: ResMut<GameRng>,
+    (tuning, movement): (Res<HyperspaceTuning>, Res<MovementTuning>),
+    time: Res<Time>,
+) {
+    for (entity, mut jump, mut transform, interpolation, has_health) in query.iter_mut() {
+        if !jump.timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        match jump.phase {
+            HyperspacePhase::Away => {
+                let asteroids: Vec<Vec3> = asteroids
+                    .iter()
+                    .map(|transform| transform.translation)
+                    .collect();
+                transform.translation = safe_point(
+                    rng.as_mut(),
+                    &asteroids,
+                    movement.world_size,
+                    tuning.safe_radius,
+                );
+                if let Some(mut interpolation) = interpolation {
+                    interpolation.teleport(&transform);
+                }
+
+                if rng.gen_bool(tuning.malfunction_chance.clamp(0.0, 1.0)) && has_health {
+                    damage_events.send(DamageEvent {
+                        source: None,
+                        target: entity,
+                        amount: tuning.malfunction_damage,
+                        kind: DamageKind::Malfunction,
+                    });
+                }
+
+                jump.phase = HyperspacePhase::ReEntering;
+                jump.timer = Timer::from_seconds(tuning.reentry_seconds.max(0.0), TimerMode::Once);
+                commands.entity(entity).remove::<ColliderDisabled>();
+            }
+            HyperspacePhase::ReEntering => {
+                commands.entity(entity).remove::<HyperspaceJump>();
+            }
+        }
+    }
+}
+
+/// A random point inside the play area at least `safe_radius` from every asteroid.  If none turns up, the point
+/// furthest from the asteroids is used, so a jump is never quite without risk.
+fn safe_point(rng: &mut GameRng, asteroids: &[Vec3], world_size: f32, safe_radius: f32) -> Vec3 {
+    let mut best = Vec3::ZERO;
+    let mut best_clearance = f32::NEG_INFINITY;
+
+    // A play area with no room in it only has the middle to come out at.
+    let max_distance = world_size * 0.8;
+    if max_distance.is_nan() || max_distance <= 0.0 {
+        return best;
+    }
+
+    for _ in 0..SAFE_POINT_ATTEMPTS {
+        let point = random_2d_unit_vector(rng) * rng.gen_range(0.0..max_distance);
+        let clearance = asteroids
+            .iter()
+            .map(|asteroid| asteroid.distance(point))
+            .fold(f32::INFINITY, f32::min);
+
+        if clearance >= safe_radius {
+            return point;
+        }
+        if clearance > best_clearance {
+            best = point;
+            best_clearance = clearance;
+        }
+    }
+
+    best
+}
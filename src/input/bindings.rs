@@ -0,0 +1,197 @@
+use std::{fmt, fs, io, path::Path, path::PathBuf};
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use super::InputAction;
+
+/// Which keys trigger each action.  Each action can have any number of keys.
+///
+/// Bindings are read from a RON file mapping actions to lists of keys.  Actions left out of the file keep their
+/// default keys:
+///
+/// ```ron
+/// {
+///     Thrust: [KeyW, ArrowUp],
+///     Fire: [Space, Enter],
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputBindings {
+    keys: HashMap<InputAction, Vec<KeyCode>>,
+}
+
+impl InputBindings {
+    /// The keys bound to an action.
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        self.keys
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Replace the keys bound to an action.
+    pub fn bind(&mut self, action: InputAction, keys: Vec<KeyCode>) {
+        self.keys.insert(action, keys);
+    }
+
+    /// A short name for the first key bound to an action, for showing to the player.
+    pub fn key_name(&self, action: InputAction) -> String {
+        match self.keys(action).first() {
+            Some(key) => key_name(*key),
+            None => "(unbound)".to_string(),
+        }
+    }
+
+    /// Read bindings from a RON file on top of the defaults, and check them for conflicts.
+    pub fn load(path: &Path) -> Result<Self, BindingsError> {
+        let contents = fs::read_to_string(path)?;
+        let overrides: HashMap<InputAction, Vec<KeyCode>> = ron::from_str(&contents)?;
+
+        let mut bindings = Self::default();
+        for (action, keys) in overrides {
+            bindings.bind(action, keys);
+        }
+
+        bindings.validate()?;
+        Ok(bindings)
+    }
+
+    /// Make sure no key is bound to two actions that can be used at the same time.
+    pub fn validate(&self) -> Result<(), BindingsError> {
+        for (i, first) in InputAction::ALL.iter().enumerate() {
+            for second in &InputAction::ALL[i + 1..] {
+                if !first.shares_context_with(*second) {
+                    continue;
+                }
+
+                let shared_key = self
+                    .keys(*first)
+                    .iter()
+                    .find(|key| self.keys(*second).contains(key));
+
+                if let Some(key) = shared_key {
+                    return Err(BindingsError::Conflict {
+                        key: *key,
+                        actions: (*first, *second),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let keys = HashMap::from([
+            (InputAction::Thrust, vec![KeyCode::KeyW, KeyCode::ArrowUp]),
+            (
+                InputAction::RotateLeft,
+                vec![KeyCode::KeyA, KeyCode::ArrowLeft],
+            ),
+            (
+                InputAction::RotateRight,
+                vec![KeyCode::KeyD, KeyCode::ArrowRight],
+            ),
+            (InputAction::RollLeft, vec![KeyCode::ShiftLeft]),
+            (InputAction::RollRight, vec![KeyCode::ControlLeft]),
+            (InputAction::Fire, vec![KeyCode::Space]),
+            (InputAction::CycleWeapon, vec![KeyCode::KeyE]),
+            (InputAction::Shield, vec![KeyCode::Tab]),
+            (
+                InputAction::Hyperspace,
+                vec![KeyCode::KeyS, KeyCode::ArrowDown],
+            ),
+            (InputAction::Pause, vec![KeyCode::Escape]),
+            (InputAction::Confirm, vec![KeyCode::Space]),
+            (InputAction::Quit, vec![KeyCode::KeyQ]),
+            (InputAction::FastForward, vec![KeyCode::KeyF]),
+        ]);
+
+        Self { keys }
+    }
+}
+
+/// Why a bindings file couldn't be used.
+#[derive(Debug)]
+pub enum BindingsError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    /// The same key is bound to two actions that are used at the same time.
+    Conflict {
+        key: KeyCode,
+        actions: (InputAction, InputAction),
+    },
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingsError::Io(error) => write!(f, "couldn't read the bindings file: {error}"),
+            BindingsError::Parse(error) => write!(f, "couldn't parse the bindings file: {error}"),
+            BindingsError::Conflict { key, actions } => write!(
+                f,
+                "{} is bound to both {:?} and {:?}",
+                key_name(*key),
+                actions.0,
+                actions.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BindingsError {}
+
+impl From<io::Error> for BindingsError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for BindingsError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+/// Where the player's bindings file lives, in their config directory.
+pub fn default_bindings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("portal-asteroids").join("bindings.ron"))
+}
+
+/// The file bindings are loaded from at startup.  When [None], or the file is missing or broken, the defaults are
+/// used.
+#[derive(Resource, Debug)]
+pub(super) struct BindingsPath(pub Option<PathBuf>);
+
+/// Load the player's bindings, falling back to the defaults.
+pub(super) fn load_bindings(path: Res<BindingsPath>, mut bindings: ResMut<InputBindings>) {
+    let Some(path) = &path.0 else {
+        return;
+    };
+
+    match InputBindings::load(path) {
+        Ok(loaded) => {
+            info!("Loaded key bindings from {}", path.display());
+            *bindings = loaded;
+        }
+        Err(BindingsError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {
+            info!("No key bindings at {}, using the defaults", path.display());
+        }
+        Err(error) => {
+            warn!("Using the default key bindings, {error}");
+        }
+    }
+}
+
+/// Turn `KeyW` into `W`, `Digit1` into `1` and so on.
+fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
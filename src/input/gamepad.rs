@@ -0,0 +1,129 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::prelude::*;
+
+use super::InputAction;
+use crate::state::GameState;
+
+/// How far the stick has to move before the spaceship starts turning.
+const STICK_DEAD_ZONE: f32 = 0.15;
+/// How far a trigger has to be pulled before it counts.
+const TRIGGER_DEAD_ZONE: f32 = 0.05;
+
+/// The gamepad the player is using.  The first one to connect is picked up automatically.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveGamepad(pub Option<Gamepad>);
+
+/// The button that triggers an action on a gamepad, for actions that aren't on an axis.
+fn button_for(action: InputAction) -> Option<GamepadButtonType> {
+    match action {
+        InputAction::RollLeft => Some(GamepadButtonType::LeftTrigger),
+        InputAction::RollRight => Some(GamepadButtonType::RightTrigger),
+        InputAction::Fire => Some(GamepadButtonType::South),
+        InputAction::CycleWeapon => Some(GamepadButtonType::West),
+        InputAction::Shield => Some(GamepadButtonType::East),
+        InputAction::Hyperspace => Some(GamepadButtonType::DPadDown),
+        InputAction::Pause => Some(GamepadButtonType::Start),
+        InputAction::Confirm => Some(GamepadButtonType::South),
+        InputAction::Quit => Some(GamepadButtonType::Select),
+        InputAction::FastForward => Some(GamepadButtonType::North),
+        InputAction::Thrust | InputAction::RotateLeft | InputAction::RotateRight => None,
+    }
+}
+
+/// Reads actions from the active gamepad.
+#[derive(SystemParam)]
+pub(super) struct GamepadActions<'w> {
+    active: Res<'w, ActiveGamepad>,
+    buttons: Res<'w, ButtonInput<GamepadButton>>,
+    button_axes: Res<'w, Axis<GamepadButton>>,
+    axes: Res<'w, Axis<GamepadAxis>>,
+}
+
+impl GamepadActions<'_> {
+    /// How strongly the action is held on the gamepad, from 0.0 to 1.0.
+    pub fn value(&self, action: InputAction) -> f32 {
+        let Some(gamepad) = self.active.0 else {
+            return 0.0;
+        };
+
+        // The left stick turns the spaceship, the further it's pushed the faster it turns.
+        let stick_x = self
+            .axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.0);
+        let stick_x = apply_dead_zone(stick_x, STICK_DEAD_ZONE);
+
+        match action {
+            InputAction::RotateLeft => (-stick_x).max(0.0),
+            InputAction::RotateRight => stick_x.max(0.0),
+            // The right trigger gives analog thrust.
+            InputAction::Thrust => {
+                let trigger = self
+                    .button_axes
+                    .get(GamepadButton::new(
+                        gamepad,
+                        GamepadButtonType::RightTrigger2,
+                    ))
+                    .unwrap_or(0.0);
+                apply_dead_zone(trigger, TRIGGER_DEAD_ZONE).max(0.0)
+            }
+            action => {
+                let pressed = button_for(action).is_some_and(|button_type| {
+                    self.buttons
+                        .pressed(GamepadButton::new(gamepad, button_type))
+                });
+                if pressed {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Ignore small movements around the centre, then scale the rest back up to the full range.
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() <= dead_zone {
+        return 0.0;
+    }
+
+    value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
+}
+
+/// Pick up newly connected gamepads, and pause the game if the one being used is unplugged.
+pub(super) fn handle_gamepad_connections(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut active: ResMut<ActiveGamepad>,
+    gamepads: Res<Gamepads>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for GamepadConnectionEvent {
+        gamepad,
+        connection,
+    } in connection_events.read()
+    {
+        match connection {
+            GamepadConnection::Connected(info) => {
+                if active.0.is_none() {
+                    info!("Using gamepad {}", info.name);
+                    active.0 = Some(*gamepad);
+                }
+            }
+            GamepadConnection::Disconnected => {
+                if active.0 != Some(*gamepad) {
+                    continue;
+                }
+
+                warn!("The gamepad was disconnected");
+                active.0 = gamepads.iter().find(|other| other != gamepad);
+
+                if *state.get() == GameState::InGame {
+                    next_state.set(GameState::Paused);
+                }
+            }
+        }
+    }
+}
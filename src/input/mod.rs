@@ -0,0 +1,204 @@
+mod bindings;
+mod gamepad;
+
+pub use bindings::{default_bindings_path, BindingsError, InputBindings};
+pub use gamepad::ActiveGamepad;
+
+use std::path::PathBuf;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+use bindings::{load_bindings, BindingsPath};
+use gamepad::{handle_gamepad_connections, GamepadActions};
+
+/// Turns raw device input into [InputAction]s, which the rest of the game reads instead of keys.
+pub struct InputActionPlugin {
+    /// The key bindings file to load at startup.  When [None], the default bindings are used.
+    pub bindings_path: Option<PathBuf>,
+}
+
+impl Default for InputActionPlugin {
+    fn default() -> Self {
+        Self {
+            bindings_path: default_bindings_path(),
+        }
+    }
+}
+
+impl Plugin for InputActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBindings>()
+            .insert_resource(BindingsPath(self.bindings_path.clone()))
+            .add_systems(PreStartup, load_bindings)
+            .init_resource::<ActiveGamepad>()
+            .init_resource::<ActionState>()
+            .init_resource::<TickActions>()
+            .add_systems(
+                PreUpdate,
+                (
+                    handle_gamepad_connections,
+                    read_device_actions,
+                    buffer_tick_actions,
+                )
+                    .chain()
+                    .in_set(ActionInputSet)
+                    .after(InputSystem),
+            )
+            .add_systems(FixedLast, clear_tick_actions);
+    }
+}
+
+/// The systems that fill in [ActionState] and [TickActions] each frame.  Anything else driving the actions, like
+/// bots or replays, should run after this.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct ActionInputSet;
+
+/// Something the player can do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    Thrust,
+    RotateLeft,
+    RotateRight,
+    RollLeft,
+    RollRight,
+    Fire,
+    CycleWeapon,
+    Shield,
+    Hyperspace,
+    Pause,
+    Confirm,
+    Quit,
+    /// Speed up a replay.
+    FastForward,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 13] = [
+        InputAction::Thrust,
+        InputAction::RotateLeft,
+        InputAction::RotateRight,
+        InputAction::RollLeft,
+        InputAction::RollRight,
+        InputAction::Fire,
+        InputAction::CycleWeapon,
+        InputAction::Shield,
+        InputAction::Hyperspace,
+        InputAction::Pause,
+        InputAction::Confirm,
+        InputAction::Quit,
+        InputAction::FastForward,
+    ];
+
+    /// Whether the action is used in menus rather than while flying the spaceship.  [InputAction::Pause] is both.
+    pub fn is_menu_action(self) -> bool {
+        matches!(
+            self,
+            InputAction::Pause
+                | InputAction::Confirm
+                | InputAction::Quit
+                | InputAction::FastForward
+        )
+    }
+
+    /// Whether the action is used while flying the spaceship.
+    pub fn is_gameplay_action(self) -> bool {
+        !matches!(
+            self,
+            InputAction::Confirm | InputAction::Quit | InputAction::FastForward
+        )
+    }
+
+    /// Whether both actions can be used at the same time, so they can't share a key.
+    pub fn shares_context_with(self, other: InputAction) -> bool {
+        (self.is_menu_action() && other.is_menu_action())
+            || (self.is_gameplay_action() && other.is_gameplay_action())
+    }
+}
+
+/// Which actions are being held and how strongly, and which have just been pressed.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionState {
+    values: HashMap<InputAction, f32>,
+    just_pressed: HashSet<InputAction>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.values.contains_key(&action)
+    }
+
+    /// How strongly an action is held, from 0.0 to 1.0.  Keys and buttons are always 1.0 when held, while sticks and
+    /// triggers can be anywhere in between.
+    pub fn value(&self, action: InputAction) -> f32 {
+        self.values.get(&action).copied().unwrap_or(0.0)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Hold an action with the given strength, or release it if the value is zero.  It counts as just pressed if it
+    /// wasn't already held.
+    pub fn set_value(&mut self, action: InputAction, value: f32) {
+        if value <= 0.0 {
+            self.values.remove(&action);
+        } else if self.values.insert(action, value.min(1.0)).is_none() {
+            self.just_pressed.insert(action);
+        }
+    }
+
+    /// Start holding an action fully.
+    pub fn press(&mut self, action: InputAction) {
+        self.set_value(action, 1.0);
+    }
+
+    pub fn release(&mut self, action: InputAction) {
+        self.set_value(action, 0.0);
+    }
+
+    /// Forget which actions were just pressed, leaving the held actions alone.
+    pub fn clear_just_pressed(&mut self) {
+        self.just_pressed.clear();
+    }
+}
+
+/// The actions for the gameplay systems in [FixedUpdate].  Presses are held on to until a tick has seen them, so
+/// none are lost or repeated however the frame rate and tick rate line up.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Deref, DerefMut)]
+pub struct TickActions(pub ActionState);
+
+/// Update the frame's [ActionState] from the keyboard and gamepad, whichever is pushed hardest.
+fn read_device_actions(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    gamepad: GamepadActions,
+    mut actions: ResMut<ActionState>,
+) {
+    actions.clear_just_pressed();
+
+    for action in InputAction::ALL {
+        let keyboard_value = if keyboard_input.any_pressed(bindings.keys(action).iter().copied()) {
+            1.0
+        } else {
+            0.0
+        };
+
+        actions.set_value(action, f32::max(keyboard_value, gamepad.value(action)));
+    }
+}
+
+/// Carry the frame's actions over to the next tick.
+fn buffer_tick_actions(actions: Res<ActionState>, mut tick_actions: ResMut<TickActions>) {
+    tick_actions.values.clone_from(&actions.values);
+    tick_actions
+        .just_pressed
+        .extend(actions.just_pressed.iter().copied());
+}
+
+/// A tick has seen the presses now.
+fn clear_tick_actions(mut tick_actions: ResMut<TickActions>) {
+    tick_actions.clear_just_pressed();
+}
@@ -0,0 +1,91 @@
+#![allow(clippy::type_complexity)]
+
+pub mod asset_loader;
+pub mod asteroids;
+pub mod camera;
+pub mod collision;
+pub mod debug;
+pub mod despawn;
+pub mod director;
+pub mod enemy;
+pub mod headless;
+pub mod health;
+pub mod hyperspace;
+pub mod input;
+pub mod movement;
+pub mod powerup;
+pub mod replay;
+pub mod ring;
+pub mod rng;
+pub mod schedule;
+pub mod scoreboard;
+pub mod shield;
+pub mod spaceship;
+pub mod state;
+pub mod tuning;
+pub mod ui;
+pub mod wave;
+pub mod weapon;
+
+use asset_loader::AssetLoaderPlugin;
+use asteroids::AsteroidPlugin;
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use camera::CameraPlugin;
+use collision::CollisionPlugin;
+use despawn::DespawnPlugin;
+use director::DirectorPlugin;
+use enemy::EnemyPlugin;
+use health::HealthPlugin;
+use hyperspace::HyperspacePlugin;
+use input::InputActionPlugin;
+use movement::MovementPlugin;
+use powerup::PowerUpPlugin;
+use replay::ReplayPlugin;
+use ring::RingPlugin;
+use rng::RngPlugin;
+use schedule::SchedulePlugin;
+use scoreboard::ScoreboardPlugin;
+use shield::ShieldPlugin;
+use spaceship::SpaceshipPlugin;
+use state::GameStatePlugin;
+use tuning::TuningPlugin;
+use ui::UiPlugin;
+use wave::WavePlugin;
+use weapon::WeaponPlugin;
+
+/// All of the game's plugins.  Individual plugins can be turned off with [PluginGroupBuilder::disable], e.g.
+/// `AsteroidsPlugins.build().disable::<RingPlugin>()`.
+pub struct AsteroidsPlugins;
+
+impl PluginGroup for AsteroidsPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(SchedulePlugin::default())
+            .add(RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule())
+            .add(InputActionPlugin::default())
+            .add(GameStatePlugin)
+            .add(RngPlugin::default())
+            .add(TuningPlugin::default())
+            .add(ReplayPlugin::default())
+            .add(HealthPlugin)
+            .add(ShieldPlugin)
+            .add(HyperspacePlugin)
+            .add(AssetLoaderPlugin)
+            .add(SpaceshipPlugin)
+            .add(WeaponPlugin)
+            .add(CollisionPlugin)
+            .add(AsteroidPlugin)
+            .add(WavePlugin)
+            .add(DirectorPlugin)
+            .add(EnemyPlugin)
+            .add(PowerUpPlugin)
+            .add(CameraPlugin)
+            .add(MovementPlugin)
+            .add(DespawnPlugin)
+            .add(UiPlugin)
+            .add(ScoreboardPlugin)
+            .add(RingPlugin)
+    }
+}
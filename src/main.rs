@@ -1,39 +1,109 @@
-#![allow(clippy::type_complexity)]
+use std::path::{Path, PathBuf};
 
-mod asset_loader;
-mod asteroids;
-mod camera;
-mod collision;
-mod debug;
-mod despawn;
-mod health;
-mod movement;
-mod ring;
-mod schedule;
-mod scoreboard;
-mod spaceship;
-mod state;
-mod ui;
-
-use asset_loader::AssetLoaderPlugin;
-use asteroids::AsteroidPlugin;
 use bevy::prelude::*;
 use bevy::window::WindowResolution;
-use bevy_rapier3d::prelude::*;
-use camera::CameraPlugin;
-use collision::CollisionPlugin;
-// use debug::DebugPlugin;
-use despawn::DespawnPlugin;
-use health::HealthPlugin;
-use movement::MovementPlugin;
-use ring::RingPlugin;
-use schedule::SchedulePlugin;
-use scoreboard::ScoreboardPlugin;
-use spaceship::SpaceshipPlugin;
-use state::GameStatePlugin;
-use ui::UiPlugin;
+// use portal_asteroids::debug::DebugPlugin;
+use portal_asteroids::{
+    headless::{run_headless, verify_replay},
+    replay::{Replay, ReplayPlugin},
+    rng::RngPlugin,
+    tuning::{TuningPlugin, TuningSource},
+    AsteroidsPlugins,
+};
+
+const USAGE: &str =
+    "usage: portal-asteroids [--headless <ticks>] [--seed <seed>] [--record <file>] \
+                     [--replay <file> | --verify <file>]";
+
+/// Options given on the command line.
+#[derive(Debug, Default)]
+struct Args {
+    /// Run this many ticks without a window, then print the result.
+    headless_ticks: Option<u32>,
+    /// Play every game with this seed.
+    seed: Option<u64>,
+    /// Record each game to this replay file.
+    record: Option<PathBuf>,
+    /// Play this replay file back.
+    replay: Option<PathBuf>,
+    /// Check that this replay file plays back to its recorded score, without a window.
+    verify: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--headless" => {
+                let ticks = iter.next().ok_or("--headless needs a number of ticks")?;
+                let ticks = ticks
+                    .parse()
+                    .map_err(|_| format!("'{ticks}' isn't a valid number of ticks"))?;
+                args.headless_ticks = Some(ticks);
+            }
+            "--seed" => {
+                let seed = iter.next().ok_or("--seed needs a number")?;
+                let seed = seed
+                    .parse()
+                    .map_err(|_| format!("'{seed}' isn't a valid seed"))?;
+                args.seed = Some(seed);
+            }
+            "--record" => {
+                args.record = Some(iter.next().ok_or("--record needs a file")?.into());
+            }
+            "--replay" => {
+                args.replay = Some(iter.next().ok_or("--replay needs a file")?.into());
+            }
+            "--verify" => {
+                args.verify = Some(iter.next().ok_or("--verify needs a file")?.into());
+            }
+            other => return Err(format!("unknown argument '{other}'")),
+        }
+    }
+
+    Ok(args)
+}
 
 fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(path) = &args.verify {
+        let verification = verify_replay(load_replay(path));
+        println!("{verification}");
+        if !verification.matches() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(ticks) = args.headless_ticks {
+        println!("{}", run_headless(ticks, args.seed));
+        return;
+    }
+
+    let replay_plugin = ReplayPlugin {
+        record_path: args.record,
+        playback: args.replay.as_deref().map(load_replay),
+    };
+    // A replay brings its own tuning, which mustn't change under it.  One being recorded only keeps the tuning the
+    // game started with, so edits part way through would stop it playing back the same.
+    let tuning_plugin = TuningPlugin {
+        source: match (&replay_plugin.playback, &replay_plugin.record_path) {
+            (Some(_), _) => TuningSource::Defaults,
+            (None, Some(_)) => TuningSource::File,
+            (None, None) => TuningSource::HotReloadedFile,
+        },
+    };
+
     App::new()
         // Bevy built-ins.
         .add_plugins(DefaultPlugins.set(WindowPlugin {
@@ -49,23 +119,25 @@ fn main() {
             color: Color::default(),
             brightness: 500.0,
         })
-        // Rapier Physics.
-        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
-        // .add_plugins(RapierDebugRenderPlugin::default())
+        // .add_plugins(bevy_rapier3d::prelude::RapierDebugRenderPlugin::default())
         // Game plugins.
-        .add_plugins(SchedulePlugin)
-        .add_plugins(GameStatePlugin)
-        .add_plugins(HealthPlugin)
-        .add_plugins(AssetLoaderPlugin)
-        .add_plugins(SpaceshipPlugin)
-        .add_plugins(CollisionPlugin)
-        .add_plugins(AsteroidPlugin)
-        .add_plugins(CameraPlugin)
-        .add_plugins(MovementPlugin)
-        .add_plugins(DespawnPlugin)
-        .add_plugins(UiPlugin)
-        .add_plugins(ScoreboardPlugin)
-        .add_plugins(RingPlugin)
+        .add_plugins(
+            AsteroidsPlugins
+                .build()
+                .set(RngPlugin { seed: args.seed })
+                .set(tuning_plugin)
+                .set(replay_plugin),
+        )
         // .add_plugins(DebugPlugin)
         .run();
 }
+
+fn load_replay(path: &Path) -> Replay {
+    match Replay::load(path) {
+        Ok(replay) => replay,
+        Err(error) => {
+            eprintln!("{}: {error}", path.display());
+            std::process::exit(2);
+        }
+    }
+}
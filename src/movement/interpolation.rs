@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+/// The entity's simulated transform at the last two fixed ticks.  Between ticks, the rendered transform is blended
+/// between the two so movement looks smooth at any frame rate.
+#[derive(Component, Debug)]
+pub struct TransformInterpolation {
+    previous: Transform,
+    current: Transform,
+}
+
+impl TransformInterpolation {
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Where the entity was at the start of the tick, or where it jumped to if it has teleported since.
+    pub fn previous(&self) -> &Transform {
+        &self.previous
+    }
+
+    /// Where the last tick left the entity, before it's blended for rendering.
+    pub fn current(&self) -> &Transform {
+        &self.current
+    }
+
+    /// Stop the entity being drawn sliding across the screen after it has jumped to a new place this tick.
+    pub fn teleport(&mut self, transform: &Transform) {
+        self.previous = *transform;
+    }
+
+    /// Move the entity from outside the simulation, between ticks.  Unlike [TransformInterpolation::teleport], the
+    /// next tick starts from here rather than from where the last one left it.
+    pub fn reset(&mut self, transform: &Transform) {
+        self.previous = *transform;
+        self.current = *transform;
+    }
+}
+
+/// Put the simulated transform back before a tick, in place of the interpolated one that was rendered.
+pub fn restore_simulated_transform(
+    mut query: Query<(&mut TransformInterpolation, &mut Transform)>,
+) {
+    for (mut interpolation, mut transform) in query.iter_mut() {
+        *transform = interpolation.current;
+        interpolation.previous = interpolation.current;
+    }
+}
+
+/// Remember where the tick left the entity.
+pub fn record_simulated_transform(mut query: Query<(&mut TransformInterpolation, &Transform)>) {
+    for (mut interpolation, transform) in query.iter_mut() {
+        interpolation.current = *transform;
+    }
+}
+
+/// Blend between the last two ticks by how far we are through the next one.
+pub fn interpolate_transform(
+    mut query: Query<(&TransformInterpolation, &mut Transform)>,
+    time: Res<Time<Fixed>>,
+) {
+    let t = time.overstep_fraction();
+
+    for (interpolation, mut transform) in query.iter_mut() {
+        let TransformInterpolation { previous, current } = interpolation;
+
+        *transform = Transform {
+            translation: previous.translation.lerp(current.translation, t),
+            rotation: previous.rotation.slerp(current.rotation, t),
+            scale: previous.scale.lerp(current.scale, t),
+        };
+    }
+}
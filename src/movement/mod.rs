@@ -1,45 +1,89 @@
 mod acceleration;
 mod angular_velocity;
+mod interpolation;
 mod moving_object_bundle;
 mod velocity;
 
 pub use acceleration::Acceleration;
 pub use angular_velocity::AngularVelocity;
+pub use interpolation::TransformInterpolation;
 pub use moving_object_bundle::MovingObjectBundle;
 pub use velocity::Velocity;
 
 use crate::schedule::InGameSet;
 use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use serde::{Deserialize, Serialize};
 
 /// The size of the game area in units.
 pub const WORLD_SIZE: f32 = 50.0;
 
+/// Balance numbers for the play area, loaded from the tuning file.  See [crate::tuning].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MovementTuning {
+    /// The size of the game area in units.
+    pub world_size: f32,
+}
+
+impl Default for MovementTuning {
+    fn default() -> Self {
+        Self {
+            world_size: WORLD_SIZE,
+        }
+    }
+}
+
+/// The systems that move entities, so that other [InGameSet::EntityUpdates] systems can run after them.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct MovementSet;
+
 pub struct MovementPlugin;
 
 impl Plugin for MovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                acceleration::update_velocity,
-                velocity::update_position,
-                confine_to_play_area,
-                angular_velocity::update_rotation,
+        app.init_resource::<MovementTuning>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    acceleration::update_velocity,
+                    velocity::update_position,
+                    confine_to_play_area,
+                    angular_velocity::update_rotation,
+                )
+                    .chain()
+                    .in_set(MovementSet)
+                    .in_set(InGameSet::EntityUpdates),
             )
-                .chain()
-                .in_set(InGameSet::EntityUpdates),
-        );
+            .add_systems(FixedFirst, interpolation::restore_simulated_transform)
+            .add_systems(FixedLast, interpolation::record_simulated_transform)
+            .add_systems(
+                PostUpdate,
+                interpolation::interpolate_transform.before(TransformSystem::TransformPropagate),
+            );
     }
 }
 
 #[derive(Component)]
 pub struct ConfinedToPlayArea;
 
-fn confine_to_play_area(mut query: Query<&mut Transform, With<ConfinedToPlayArea>>) {
-    for mut transform in query.iter_mut() {
+fn confine_to_play_area(
+    mut query: Query<
+        (&mut Transform, Option<&mut TransformInterpolation>),
+        With<ConfinedToPlayArea>,
+    >,
+    tuning: Res<MovementTuning>,
+) {
+    let world_size = tuning.world_size;
+
+    for (mut transform, interpolation) in query.iter_mut() {
         // Send the entity to the other side.
-        if transform.translation.distance(Vec3::ZERO) >= WORLD_SIZE {
-            transform.translation = -transform.translation.normalize_or_zero() * WORLD_SIZE - 0.01;
+        if transform.translation.distance(Vec3::ZERO) >= world_size {
+            transform.translation = -transform.translation.normalize_or_zero() * world_size - 0.01;
+
+            if let Some(mut interpolation) = interpolation {
+                interpolation.teleport(&transform);
+            }
         }
     }
 }
@@ -1,23 +1,29 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use super::{Acceleration, AngularVelocity, Velocity};
+use super::{Acceleration, AngularVelocity, TransformInterpolation, Velocity};
+use crate::collision::Faction;
 
 #[derive(Bundle)]
 pub struct MovingObjectBundle {
     pub velocity: Velocity,
     pub acceleration: Acceleration,
     pub angular_velocity: AngularVelocity,
+    pub interpolation: TransformInterpolation,
     pub model: SceneBundle,
     pub sensor: Sensor,
     pub active_collision_types: ActiveCollisionTypes,
     pub collider: Collider,
+    pub faction: Faction,
+    /// Filled in from the faction before physics sees the entity.  See [crate::collision::CollisionTuning].
+    pub collision_groups: CollisionGroups,
 }
 
 impl MovingObjectBundle {
     pub fn new(
         model: SceneBundle,
         collider: Collider,
+        faction: Faction,
         velocity: impl Into<Velocity>,
         acceleration: impl Into<Acceleration>,
         angular_velocity: impl Into<AngularVelocity>,
@@ -25,6 +31,7 @@ impl MovingObjectBundle {
         let velocity = velocity.into();
         let acceleration = acceleration.into();
         let angular_velocity = angular_velocity.into();
+        let interpolation = TransformInterpolation::new(model.transform);
         let sensor = Sensor;
         let active_collision_types = ActiveCollisionTypes::all();
 
@@ -32,10 +39,13 @@ impl MovingObjectBundle {
             velocity,
             acceleration,
             angular_velocity,
+            interpolation,
             model,
             sensor,
             active_collision_types,
             collider,
+            faction,
+            collision_groups: CollisionGroups::default(),
         }
     }
 }
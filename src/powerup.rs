@@ -0,0 +1,313 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asset_loader::SceneAssets,
+    asteroids::{random_2d_unit_vector, split_destroyed_asteroids, Asteroid},
+    collision::Faction,
+    despawn::{remove_with_component, DeathReaderSet, DespawnOnDie, DespawnTimer},
+    health::{DieEvent, HealEvent},
+    movement::{Acceleration, AngularVelocity, ConfinedToPlayArea, MovingObjectBundle},
+    rng::GameRng,
+    schedule::InGameSet,
+    shield::Shield,
+    spaceship::Spaceship,
+    state::GameState,
+    weapon::Weapon,
+};
+
+/// Drops power-ups from destroyed asteroids, hands them to the spaceship when it flies into them, and wears off the
+/// ones that only last a while.
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PowerUpTuning>()
+            .add_systems(
+                FixedUpdate,
+                // Splitting also draws from the game's RNG, so drops are always rolled after it.
+                drop_power_ups
+                    .after(split_destroyed_asteroids)
+                    .in_set(DeathReaderSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                wear_off_power_ups.in_set(InGameSet::EntityUpdates),
+            )
+            .add_systems(
+                FixedUpdate,
+                collect_power_ups.in_set(InGameSet::CollisionDetection),
+            )
+            // Power-ups have no health, so the usual game over clean-up misses them.
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                remove_with_component::<PowerUp>,
+            );
+    }
+}
+
+/// Balance numbers for power-ups, loaded from the tuning file.  See [crate::tuning].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PowerUpTuning {
+    /// The chance of a destroyed asteroid dropping a power-up, from 0.0 to 1.0.
+    pub drop_chance: f64,
+    /// How long a power-up drifts about before it's gone.
+    pub lifespan_seconds: f32,
+    pub drift_speed: f32,
+    pub radius: f32,
+    /// How much health a repair gives back.
+    pub repair_amount: f32,
+    /// How far over the spaceship's full health a repair can take it.
+    pub repair_overheal: f32,
+    /// How long rapid fire, multishot and the score multiplier last.
+    pub effect_seconds: f32,
+    /// What the weapon's fire rate is multiplied by during rapid fire.
+    pub rapid_fire_multiplier: f32,
+    /// How many more projectiles each shot fires during multishot.
+    pub multishot_extra_projectiles: u32,
+    /// The least spread, in degrees, a shot has during multishot.
+    pub multishot_spread: f32,
+    pub score_multiplier: f32,
+}
+
+impl Default for PowerUpTuning {
+    fn default() -> Self {
+        Self {
+            drop_chance: 0.15,
+            lifespan_seconds: 10.0,
+            drift_speed: 3.0,
+            radius: 1.5,
+            repair_amount: 40.0,
+            repair_overheal: 0.0,
+            effect_seconds: 10.0,
+            rapid_fire_multiplier: 2.0,
+            multishot_extra_projectiles: 2,
+            multishot_spread: 20.0,
+            score_multiplier: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    Repair,
+    ShieldRecharge,
+    RapidFire,
+    Multishot,
+    ScoreMultiplier,
+}
+
+impl PowerUpKind {
+    /// Every power-up, each as likely to drop as the others.
+    pub const ALL: [PowerUpKind; 5] = [
+        PowerUpKind::Repair,
+        PowerUpKind::ShieldRecharge,
+        PowerUpKind::RapidFire,
+        PowerUpKind::Multishot,
+        PowerUpKind::ScoreMultiplier,
+    ];
+
+    /// The name shown to the player.
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerUpKind::Repair => "Repair",
+            PowerUpKind::ShieldRecharge => "Shield Recharge",
+            PowerUpKind::RapidFire => "Rapid Fire",
+            PowerUpKind::Multishot => "Multishot",
+            PowerUpKind::ScoreMultiplier => "Score Multiplier",
+        }
+    }
+}
+
+/// A power-up waiting to be collected.
+#[derive(Component, Debug)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+}
+
+/// The timed power-ups an entity has collected, and how long each has left.  Collecting one that's already going
+/// starts it over.
+#[derive(Component, Debug, Default)]
+pub struct ActivePowerUps {
+    effects: Vec<(PowerUpKind, Timer)>,
+}
+
+impl ActivePowerUps {
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.effects.iter().any(|(active, _)| *active == kind)
+    }
+
+    /// Each power-up that's going, with how many seconds it has left, in the order they were collected.
+    pub fn remaining_secs(&self) -> impl Iterator<Item = (PowerUpKind, f32)> + '_ {
+        self.effects
+            .iter()
+            .map(|(kind, timer)| (*kind, timer.remaining_secs()))
+    }
+
+    fn start(&mut self, kind: PowerUpKind, seconds: f32) {
+        let timer = Timer::new(Duration::from_secs_f32(seconds.max(0.0)), TimerMode::Once);
+        match self.effects.iter_mut().find(|(active, _)| *active == kind) {
+            Some((_, active)) => *active = timer,
+            None => self.effects.push((kind, timer)),
+        }
+    }
+
+    /// The weapon as it fires with rapid fire and multishot taken into account.
+    pub fn boost(&self, weapon: &Weapon, tuning: &PowerUpTuning) -> Weapon {
+        let mut weapon = weapon.clone();
+        if self.is_active(PowerUpKind::RapidFire) {
+            weapon.fire_rate *= tuning.rapid_fire_multiplier.max(f32::EPSILON);
+        }
+        if self.is_active(PowerUpKind::Multishot) {
+            weapon.projectile_count =
+                weapon.projectile_count.max(1) + tuning.multishot_extra_projectiles;
+            weapon.spread = weapon.spread.max(tuning.multishot_spread);
+        }
+        weapon
+    }
+
+    /// What points are multiplied by while the score multiplier is going.
+    pub fn score_multiplier(&self, tuning: &PowerUpTuning) -> f32 {
+        if self.is_active(PowerUpKind::ScoreMultiplier) {
+            tuning.score_multiplier
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct PowerUpBundle {
+    pub moving_object_bundle: MovingObjectBundle,
+    pub power_up: PowerUp,
+    pub despawn_timer: DespawnTimer,
+    pub despawn_on_die: DespawnOnDie,
+    pub confined_to_play_area: ConfinedToPlayArea,
+}
+
+impl PowerUpBundle {
+    pub fn new(
+        kind: PowerUpKind,
+        translation: Vec3,
+        velocity: Vec3,
+        tuning: &PowerUpTuning,
+    ) -> Self {
+        let moving_object_bundle = MovingObjectBundle::new(
+            SceneBundle {
+                transform: Transform::from_translation(translation),
+                ..default()
+            },
+            Collider::ball(tuning.radius),
+            Faction::Pickup,
+            velocity,
+            Acceleration::new(Vec3::ZERO),
+            AngularVelocity::new(Vec3::new(0.0, 1.0, 0.0)),
+        );
+
+        PowerUpBundle {
+            moving_object_bundle,
+            power_up: PowerUp { kind },
+            despawn_timer: DespawnTimer::new(Duration::from_secs_f32(
+                tuning.lifespan_seconds.max(0.0),
+            )),
+            despawn_on_die: DespawnOnDie,
+            confined_to_play_area: ConfinedToPlayArea,
+        }
+    }
+}
+
+/// Destroyed asteroids sometimes leave a power-up behind, drifting off in a random direction.
+fn drop_power_ups(
+    mut commands: Commands,
+    mut die_events: EventReader<DieEvent>,
+    asteroids: Query<&Transform, With<Asteroid>>,
+    assets: Res<SceneAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<PowerUpTuning>,
+) {
+    let rng = rng.as_mut();
+
+    for DieEvent { entity, .. } in die_events.read() {
+        let Ok(transform) = asteroids.get(*entity) else {
+            continue;
+        };
+        if !rng.gen_bool(tuning.drop_chance.clamp(0.0, 1.0)) {
+            continue;
+        }
+
+        let kind = PowerUpKind::ALL[rng.gen_range(0..PowerUpKind::ALL.len())];
+        let velocity = random_2d_unit_vector(rng) * tuning.drift_speed;
+
+        commands
+            .spawn(PowerUpBundle::new(
+                kind,
+                transform.translation,
+                velocity,
+                &tuning,
+            ))
+            .with_children(|builder| {
+                builder.spawn(PbrBundle {
+                    mesh: assets.shield_bubble.clone(),
+                    material: assets.power_up_material.clone(),
+                    transform: Transform::from_scale(Vec3::splat(tuning.radius)),
+                    ..default()
+                });
+            });
+    }
+}
+
+/// Timed power-ups run out after a while.
+fn wear_off_power_ups(mut query: Query<&mut ActivePowerUps>, time: Res<Time>) {
+    for mut power_ups in query.iter_mut() {
+        for (_, timer) in power_ups.effects.iter_mut() {
+            timer.tick(time.delta());
+        }
+        power_ups.effects.retain(|(_, timer)| !timer.finished());
+    }
+}
+
+/// The spaceship collects power-ups by flying into them.  Repairs and shield recharges take effect straight away, and
+/// the rest last a while.
+fn collect_power_ups(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut heal_events: EventWriter<HealEvent>,
+    power_ups: Query<&PowerUp>,
+    mut spaceship: Query<(&mut Shield, &mut ActivePowerUps), With<Spaceship>>,
+    tuning: Res<PowerUpTuning>,
+) {
+    for event in collision_event_reader.read() {
+        let CollisionEvent::Started(entity1, entity2, _) = event else {
+            continue;
+        };
+
+        // Either entity could be the power-up.
+        let (power_up_entity, power_up, collector) =
+            match (power_ups.get(*entity1), power_ups.get(*entity2)) {
+                (Ok(power_up), _) => (*entity1, power_up, *entity2),
+                (_, Ok(power_up)) => (*entity2, power_up, *entity1),
+                _ => continue,
+            };
+        let Ok((mut shield, mut active)) = spaceship.get_mut(collector) else {
+            continue;
+        };
+
+        match power_up.kind {
+            PowerUpKind::Repair => {
+                heal_events.send(HealEvent {
+                    target: collector,
+                    amount: tuning.repair_amount,
+                    overheal: tuning.repair_overheal,
+                });
+            }
+            PowerUpKind::ShieldRecharge => shield.energy = shield.max_energy,
+            kind => active.start(kind, tuning.effect_seconds),
+        }
+        commands.entity(power_up_entity).despawn_recursive();
+    }
+}
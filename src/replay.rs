@@ -0,0 +1,344 @@
+use std::{fmt, fs, io, path::Path, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    input::{ActionState, InputAction, TickActions},
+    rng::{FixedSeed, GameSeed},
+    schedule::{set_tick_rate, TickRate},
+    scoreboard::Scoreboard,
+    state::{playing, GameState},
+    tuning::{Tuning, TuningResources},
+};
+
+/// How much faster than normal a replay plays while fast forwarding.
+const FAST_FORWARD_SPEED: f32 = 4.0;
+
+/// Records games to replay files, and plays them back.
+///
+/// A replay holds the seed, the tuning and the actions for every tick, which is everything needed to play the game
+/// out again exactly as it happened.
+#[derive(Debug, Default)]
+pub struct ReplayPlugin {
+    /// Record each game into this file, replacing the last one.
+    pub record_path: Option<PathBuf>,
+    /// Play this replay back instead of taking the player's input.
+    pub playback: Option<Replay>,
+}
+
+impl ReplayPlugin {
+    pub fn playback(replay: Replay) -> Self {
+        Self {
+            record_path: None,
+            playback: Some(replay),
+        }
+    }
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        match &self.playback {
+            Some(replay) => {
+                // Play the game the way it was set up when it was recorded.
+                app.insert_resource(FixedSeed(Some(replay.seed)));
+                replay.tuning.clone().insert_into(&mut app.world);
+                set_tick_rate(&mut app.world, replay.tick_rate);
+
+                app.insert_state(PlaybackState::Playing)
+                    .insert_resource(ReplayPlayer::new(replay.clone()));
+            }
+            None => {
+                app.init_state::<PlaybackState>();
+            }
+        }
+
+        if let Some(path) = &self.record_path {
+            app.insert_resource(ReplayRecorder::new(path.clone()));
+        }
+
+        app.add_systems(
+            OnTransition {
+                from: GameState::Start,
+                to: GameState::InGame,
+            },
+            (
+                start_recording.run_if(resource_exists::<ReplayRecorder>),
+                restart_playback.run_if(resource_exists::<ReplayPlayer>),
+            ),
+        )
+        // Every tick the simulation runs is recorded, even one the game ends part way through.
+        .add_systems(
+            FixedFirst,
+            (
+                play_tick_actions.run_if(resource_exists::<ReplayPlayer>),
+                record_tick_actions.run_if(resource_exists::<ReplayRecorder>),
+            )
+                .chain()
+                .run_if(playing),
+        )
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            (
+                save_recording.run_if(resource_exists::<ReplayRecorder>),
+                check_playback_score.run_if(resource_exists::<ReplayPlayer>),
+            ),
+        )
+        .add_systems(
+            Update,
+            toggle_fast_forward.run_if(not(in_state(PlaybackState::Live))),
+        )
+        .add_systems(OnEnter(PlaybackState::FastForward), start_fast_forward)
+        .add_systems(OnExit(PlaybackState::FastForward), stop_fast_forward);
+    }
+}
+
+/// Whether the game is being played live or from a replay.
+#[derive(Debug, Default, States, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PlaybackState {
+    #[default]
+    Live,
+    Playing,
+    FastForward,
+}
+
+/// A recorded game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub tick_rate: f64,
+    /// The tuning at the start of the game.  The tuning file isn't watched while recording, so it stays the same.
+    pub tuning: Tuning,
+    pub inputs: ReplayInputs,
+    /// The score at the end of the recorded game.
+    pub final_score: f32,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, ron::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// The actions for every tick of a game.  A run of ticks with the same actions is only stored once, which keeps
+/// replays small since the player's input rarely changes from one tick to the next.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayInputs(Vec<(u32, ActionState)>);
+
+impl ReplayInputs {
+    /// Add the actions for the next tick.
+    pub fn push(&mut self, actions: &ActionState) {
+        match self.0.last_mut() {
+            Some((ticks, last)) if last == actions => *ticks += 1,
+            _ => self.0.push((1, actions.clone())),
+        }
+    }
+
+    /// How many ticks were recorded.
+    pub fn ticks(&self) -> u32 {
+        self.0.iter().map(|(ticks, _)| ticks).sum()
+    }
+}
+
+/// Why a replay file couldn't be used.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Write(ron::Error),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "couldn't access the replay file: {error}"),
+            ReplayError::Parse(error) => write!(f, "couldn't parse the replay file: {error}"),
+            ReplayError::Write(error) => write!(f, "couldn't write the replay: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for ReplayError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<ron::Error> for ReplayError {
+    fn from(error: ron::Error) -> Self {
+        Self::Write(error)
+    }
+}
+
+/// The game being recorded.
+#[derive(Resource, Debug)]
+struct ReplayRecorder {
+    path: PathBuf,
+    tuning: Tuning,
+    inputs: ReplayInputs,
+}
+
+impl ReplayRecorder {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            tuning: Tuning::default(),
+            inputs: ReplayInputs::default(),
+        }
+    }
+}
+
+/// The replay being played back, and how far through it is.
+#[derive(Resource, Debug)]
+struct ReplayPlayer {
+    replay: Replay,
+    /// The run of ticks being played.
+    run: usize,
+    /// How many ticks of the run have been played.
+    ticks_into_run: u32,
+}
+
+impl ReplayPlayer {
+    fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            run: 0,
+            ticks_into_run: 0,
+        }
+    }
+
+    /// The actions for the next tick, or [None] once the replay has finished.
+    fn next_actions(&mut self) -> Option<&ActionState> {
+        let runs = &self.replay.inputs.0;
+
+        while let Some((ticks, _)) = runs.get(self.run) {
+            if self.ticks_into_run < *ticks {
+                break;
+            }
+            self.run += 1;
+            self.ticks_into_run = 0;
+        }
+
+        let (_, actions) = runs.get(self.run)?;
+        self.ticks_into_run += 1;
+        Some(actions)
+    }
+
+    /// Whether every recorded tick has been played.
+    fn finished(&self) -> bool {
+        let ticks_left: u32 = self.replay.inputs.0[self.run..]
+            .iter()
+            .map(|(ticks, _)| ticks)
+            .sum();
+        ticks_left <= self.ticks_into_run
+    }
+}
+
+fn start_recording(mut recorder: ResMut<ReplayRecorder>, tuning: TuningResources) {
+    recorder.tuning = tuning.current();
+    recorder.inputs = ReplayInputs::default();
+}
+
+fn restart_playback(mut player: ResMut<ReplayPlayer>) {
+    player.run = 0;
+    player.ticks_into_run = 0;
+}
+
+/// Drive the tick from the replay instead of the player's devices, and end the game when the replay runs out.
+fn play_tick_actions(
+    mut player: ResMut<ReplayPlayer>,
+    mut tick_actions: ResMut<TickActions>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    match player.next_actions() {
+        Some(actions) => tick_actions.0.clone_from(actions),
+        None => {
+            *tick_actions = TickActions::default();
+            next_state.set(GameState::GameOver);
+        }
+    }
+}
+
+fn record_tick_actions(mut recorder: ResMut<ReplayRecorder>, tick_actions: Res<TickActions>) {
+    recorder.inputs.push(&tick_actions);
+}
+
+fn save_recording(
+    recorder: Res<ReplayRecorder>,
+    seed: Res<GameSeed>,
+    tick_rate: Res<TickRate>,
+    scoreboard: Res<Scoreboard>,
+) {
+    let replay = Replay {
+        seed: seed.0,
+        tick_rate: tick_rate.0,
+        tuning: recorder.tuning.clone(),
+        inputs: recorder.inputs.clone(),
+        final_score: scoreboard.score,
+    };
+
+    match replay.save(&recorder.path) {
+        Ok(()) => info!("Saved the replay to {}", recorder.path.display()),
+        Err(error) => warn!(
+            "Couldn't save the replay to {}, {error}",
+            recorder.path.display()
+        ),
+    }
+}
+
+fn check_playback_score(player: Res<ReplayPlayer>, scoreboard: Res<Scoreboard>) {
+    if !player.finished() {
+        info!("The replay was stopped before the end");
+    } else if scoreboard.score == player.replay.final_score {
+        info!(
+            "The replay finished with the recorded score of {}",
+            scoreboard.score
+        );
+    } else {
+        warn!(
+            "The replay finished with a score of {}, but {} was recorded",
+            scoreboard.score, player.replay.final_score
+        );
+    }
+}
+
+fn toggle_fast_forward(
+    actions: Res<ActionState>,
+    state: Res<State<PlaybackState>>,
+    mut next_state: ResMut<NextState<PlaybackState>>,
+) {
+    if !actions.just_pressed(InputAction::FastForward) {
+        return;
+    }
+
+    match state.get() {
+        PlaybackState::Playing => next_state.set(PlaybackState::FastForward),
+        PlaybackState::FastForward => next_state.set(PlaybackState::Playing),
+        PlaybackState::Live => (),
+    }
+}
+
+fn start_fast_forward(mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(FAST_FORWARD_SPEED);
+}
+
+fn stop_fast_forward(mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(1.0);
+}
@@ -1,6 +1,6 @@
 use std::f32::consts::PI;
 
-use crate::{asset_loader::SceneAssets, movement::WORLD_SIZE};
+use crate::{asset_loader::SceneAssets, movement::MovementTuning};
 use bevy::prelude::*;
 
 const SATELLITES: i32 = 15;
@@ -13,11 +13,17 @@ impl Plugin for RingPlugin {
     }
 }
 
-fn spawn_satellites(mut commands: Commands, asset_server: Res<SceneAssets>) {
+fn spawn_satellites(
+    mut commands: Commands,
+    asset_server: Res<SceneAssets>,
+    tuning: Res<MovementTuning>,
+) {
+    let world_size = tuning.world_size;
+
     for i in 0..SATELLITES {
         // Work out the 2D position of this satellite.
         let thing = 2.0 * PI / (SATELLITES as f32) * (i as f32);
-        let point = (f32::cos(thing) * WORLD_SIZE, f32::sin(thing) * WORLD_SIZE);
+        let point = (f32::cos(thing) * world_size, f32::sin(thing) * world_size);
 
         // Spawn a satellite at that position.
         commands.spawn((SceneBundle {
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use crate::state::GameState;
+
+/// Provides a seeded random number generator for gameplay, so that games can be reproduced.
+#[derive(Debug, Default)]
+pub struct RngPlugin {
+    /// Use this seed for every game.  When [None], each game gets a new random seed.
+    pub seed: Option<u64>,
+}
+
+impl RngPlugin {
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        let seed = self.seed.unwrap_or_else(rand::random);
+
+        app.insert_resource(FixedSeed(self.seed))
+            .insert_resource(GameSeed(seed))
+            .insert_resource(GameRng::new(seed))
+            .add_systems(
+                OnTransition {
+                    from: GameState::Start,
+                    to: GameState::InGame,
+                },
+                reseed_game_rng,
+            );
+    }
+}
+
+/// The seed chosen on the command line or in config, if there was one.
+#[derive(Resource, Debug)]
+pub(crate) struct FixedSeed(pub Option<u64>);
+
+/// The seed the current game was started with.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameSeed(pub u64);
+
+/// The random number generator all gameplay randomness should come from.
+#[derive(Resource, Debug)]
+pub struct GameRng(StdRng);
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// Pick the seed for a new game and reset the generator with it.
+fn reseed_game_rng(
+    fixed_seed: Res<FixedSeed>,
+    mut game_seed: ResMut<GameSeed>,
+    mut rng: ResMut<GameRng>,
+) {
+    let seed = fixed_seed.0.unwrap_or_else(rand::random);
+    *game_seed = GameSeed(seed);
+    *rng = GameRng::new(seed);
+}
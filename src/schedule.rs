@@ -1,7 +1,12 @@
 use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
 
-use crate::state::GameState;
+use crate::state::playing;
 
+/// How many times a second the game simulation is stepped by default.
+pub const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// The gameplay systems, which run in order in [FixedUpdate] while the game is being played.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum InGameSet {
     UserInput,
@@ -10,12 +15,30 @@ pub enum InGameSet {
     DespawnEntities,
 }
 
-pub struct SchedulePlugin;
+/// How many times a second the game simulation is being stepped.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct TickRate(pub f64);
+
+/// Steps the game simulation on a fixed timestep, independent of the frame rate.
+pub struct SchedulePlugin {
+    /// How many times a second the game simulation is stepped.
+    pub tick_rate: f64,
+}
+
+impl Default for SchedulePlugin {
+    fn default() -> Self {
+        Self {
+            tick_rate: DEFAULT_TICK_RATE,
+        }
+    }
+}
 
 impl Plugin for SchedulePlugin {
     fn build(&self, app: &mut App) {
+        set_tick_rate(&mut app.world, self.tick_rate);
+
         app.configure_sets(
-            Update,
+            FixedUpdate,
             (
                 InGameSet::DespawnEntities,
                 InGameSet::UserInput,
@@ -23,13 +46,37 @@ impl Plugin for SchedulePlugin {
                 InGameSet::CollisionDetection,
             )
                 .chain()
-                .run_if(in_state(GameState::InGame)),
+                .run_if(playing),
+        )
+        // Physics picks up where entities have moved to, and collisions are handled once it's done.  It's held
+        // while the game is paused, so no collisions happen behind the pause screen.
+        .configure_sets(
+            FixedUpdate,
+            (
+                PhysicsSet::SyncBackend.after(InGameSet::EntityUpdates),
+                PhysicsSet::Writeback.before(InGameSet::CollisionDetection),
+                PhysicsSet::SyncBackend.run_if(playing),
+                PhysicsSet::StepSimulation.run_if(playing),
+                PhysicsSet::Writeback.run_if(playing),
+            ),
         )
         .add_systems(
-            Update,
+            FixedUpdate,
             apply_deferred
                 .after(InGameSet::DespawnEntities)
                 .before(InGameSet::UserInput),
         );
     }
 }
+
+/// Step the simulation, physics included, this many times a second.
+pub(crate) fn set_tick_rate(world: &mut World, tick_rate: f64) {
+    world.insert_resource(TickRate(tick_rate));
+    world.insert_resource(Time::<Fixed>::from_hz(tick_rate));
+    world
+        .get_resource_or_insert_with(RapierConfiguration::default)
+        .timestep_mode = TimestepMode::Fixed {
+        dt: (1.0 / tick_rate) as f32,
+        substeps: 1,
+    };
+}
@@ -1,6 +1,15 @@
 use bevy::prelude::*;
 
-use crate::{asteroids::Asteroid, health::DieEvent, schedule::InGameSet, state::GameState};
+use crate::{
+    asteroids::Asteroid,
+    collision::Faction,
+    despawn::DeathReaderSet,
+    enemy::Saucer,
+    health::DieEvent,
+    powerup::{ActivePowerUps, PowerUpTuning},
+    spaceship::Spaceship,
+    state::GameState,
+};
 
 /// Keeps the player's score.
 pub struct ScoreboardPlugin;
@@ -9,10 +18,16 @@ impl Plugin for ScoreboardPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Scoreboard::new())
             .add_systems(
-                Update,
-                destroy_asteroids_for_points.in_set(InGameSet::EntityUpdates),
+                FixedUpdate,
+                (destroy_asteroids_for_points, destroy_saucers_for_points).in_set(DeathReaderSet),
             )
-            .add_systems(OnEnter(GameState::InGame), reset_scoreboard);
+            .add_systems(
+                OnTransition {
+                    from: GameState::Start,
+                    to: GameState::InGame,
+                },
+                reset_scoreboard,
+            );
     }
 }
 
@@ -28,18 +43,53 @@ impl Scoreboard {
     }
 }
 
-/// The player's score goes up when an asteroid is destroyed.
+/// What points are multiplied by, which is more than one while the spaceship has a score multiplier going.
+fn score_multiplier(
+    spaceship: &Query<&ActivePowerUps, With<Spaceship>>,
+    tuning: &PowerUpTuning,
+) -> f32 {
+    spaceship
+        .get_single()
+        .map_or(1.0, |power_ups| power_ups.score_multiplier(tuning))
+}
+
+/// The player's score goes up when they destroy an asteroid.
 fn destroy_asteroids_for_points(
     mut die_events: EventReader<DieEvent>,
     mut scoreboard: ResMut<Scoreboard>,
     asteroids_query: Query<Entity, With<Asteroid>>,
+    factions: Query<&Faction>,
+    spaceship: Query<&ActivePowerUps, With<Spaceship>>,
+    power_up_tuning: Res<PowerUpTuning>,
 ) {
     let asteroids_killed = die_events
         .read()
-        .filter(|DieEvent { entity }| asteroids_query.get(*entity).is_ok())
+        .filter(|event| {
+            asteroids_query.contains(event.entity) && event.credited_to_player(&factions)
+        })
         .count();
 
-    scoreboard.score += asteroids_killed as f32;
+    scoreboard.score += asteroids_killed as f32 * score_multiplier(&spaceship, &power_up_tuning);
+}
+
+/// Saucers are worth a bonus when the player destroys them.
+fn destroy_saucers_for_points(
+    mut die_events: EventReader<DieEvent>,
+    mut scoreboard: ResMut<Scoreboard>,
+    saucers_query: Query<&Saucer>,
+    factions: Query<&Faction>,
+    spaceship: Query<&ActivePowerUps, With<Spaceship>>,
+    power_up_tuning: Res<PowerUpTuning>,
+) {
+    let multiplier = score_multiplier(&spaceship, &power_up_tuning);
+    for event in die_events.read() {
+        let Ok(saucer) = saucers_query.get(event.entity) else {
+            continue;
+        };
+        if event.credited_to_player(&factions) {
+            scoreboard.score += saucer.points * multiplier;
+        }
+    }
 }
 
 /// Reset the scoreboard back to zero.
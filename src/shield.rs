@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+use crate::schedule::InGameSet;
+
+/// How full a shield has to be before it can be raised again once it's run dry.
+const MIN_RAISE_FRACTION: f32 = 0.25;
+
+/// Drains and recharges shields, and shows them while they're up.
+pub struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            update_shield_energy.in_set(InGameSet::EntityUpdates),
+        )
+        .add_systems(Update, show_shield_bubbles);
+    }
+}
+
+/// An energy barrier.  While it's up, collision damage drains its energy instead of the entity's [crate::health::Health].
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Shield {
+    pub energy: f32,
+    pub max_energy: f32,
+    /// How much energy the shield uses each second it's up.
+    pub drain_per_second: f32,
+    /// How much energy the shield gets back each second it's down.
+    pub recharge_per_second: f32,
+    up: bool,
+}
+
+impl Shield {
+    /// A fully charged shield, starting down.
+    pub fn new(max_energy: f32, drain_per_second: f32, recharge_per_second: f32) -> Self {
+        Self {
+            energy: max_energy,
+            max_energy,
+            drain_per_second,
+            recharge_per_second,
+            up: false,
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.up
+    }
+
+    /// Keep the shield up for as long as it's held and has energy.  Once it runs dry, it has to recharge a bit before
+    /// it can go up again.
+    pub fn hold(&mut self, held: bool) {
+        let has_energy = if self.up {
+            self.energy > 0.0
+        } else {
+            self.energy >= self.max_energy * MIN_RAISE_FRACTION
+        };
+
+        self.up = held && has_energy;
+    }
+
+    /// Soak up as much of the damage as the shield's energy allows, returning the damage that gets through.
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        if !self.up {
+            return damage;
+        }
+
+        let absorbed = damage.min(self.energy);
+        self.energy -= absorbed;
+        if self.energy <= 0.0 {
+            self.up = false;
+        }
+
+        damage - absorbed
+    }
+
+    /// How much energy is left, from 0.0 to 1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.max_energy <= 0.0 {
+            return 0.0;
+        }
+
+        self.energy / self.max_energy
+    }
+}
+
+/// Marker component for the bubble shown around an entity while its [Shield] is up.  It should be a child of the
+/// entity.
+#[derive(Component, Debug)]
+pub struct ShieldBubble;
+
+/// Shields drain while they're up, and recharge while they're down.
+fn update_shield_energy(mut query: Query<&mut Shield>, time: Res<Time>) {
+    for mut shield in query.iter_mut() {
+        if shield.up {
+            shield.energy -= shield.drain_per_second * time.delta_seconds();
+            if shield.energy <= 0.0 {
+                shield.energy = 0.0;
+                shield.up = false;
+            }
+        } else {
+            shield.energy = (shield.energy + shield.recharge_per_second * time.delta_seconds())
+                .min(shield.max_energy);
+        }
+    }
+}
+
+fn show_shield_bubbles(
+    shields: Query<(&Shield, &Children), Changed<Shield>>,
+    mut bubbles: Query<&mut Visibility, With<ShieldBubble>>,
+) {
+    for (shield, children) in shields.iter() {
+        let visibility = if shield.up {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        for child in children.iter() {
+            if let Ok(mut bubble) = bubbles.get_mut(*child) {
+                bubble.set_if_neq(visibility);
+            }
+        }
+    }
+}
@@ -1,42 +1,109 @@
-use std::time::Duration;
-
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
 use bevy::ecs::query::QuerySingleError;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     asset_loader::SceneAssets,
-    collision::CollisionDamage,
-    despawn::{DespawnOnDie, DespawnTimer},
-    health::{DieEvent, Health},
+    asteroids::Asteroid,
+    collision::{CollisionDamage, Faction},
+    despawn::{DeathReaderSet, DespawnOnDie},
+    health::{DieEvent, Health, Invulnerable, Regeneration},
+    hyperspace::{
+        jump_to_hyperspace, HyperspaceDrive, HyperspaceJump, HyperspacePhase, HyperspaceTuning,
+    },
+    input::{InputAction, TickActions},
     movement::{Acceleration, AngularVelocity, ConfinedToPlayArea, MovingObjectBundle, Velocity},
+    powerup::{ActivePowerUps, PowerUpTuning},
     schedule::InGameSet,
+    scoreboard::Scoreboard,
+    shield::{Shield, ShieldBubble},
     state::GameState,
+    weapon::{fire_weapon, Arsenal, Weapon, WeaponKind, WeaponTuning},
 };
 
 const STARTING_TRANSLATION: Vec3 = Vec3::new(0., 0.0, -20.);
-pub const SPACESHIP_HEALTH: f32 = 150.0;
+const SPACESHIP_HEALTH: f32 = 150.0;
 const SPACESHIP_SPEED: f32 = 30.0;
-const SPACESHIP_ACCELERATION: f32 = 1.0;
+const SPACESHIP_ACCELERATION: f32 = 60.0;
 const SPACESHIP_ROTATION_SPEED: f32 = 2.5;
-const SPACESHIP_ROLL_SPEED: f32 = 0.1;
-const MISSILE_SPEED: f32 = 50.0;
-const MISSILE_LIFESPAN_MILLIS: u64 = 3000;
-const MISSILE_FORWARD_SCALAR: f32 = 7.5;
-const WEAPON_FIRE_RATE: f32 = 10.0;
+/// Radians a second, the same as the 0.1 radians a frame the spaceship used to roll at 60 frames a second.
+const SPACESHIP_ROLL_SPEED: f32 = 6.0;
+const SHIELD_RADIUS: f32 = 7.0;
+/// How long the spaceship spends shown and then hidden while it blinks.
+const BLINK_SECONDS: f32 = 0.15;
+
+/// Balance numbers for the spaceship, loaded from the tuning file.  See [crate::tuning].
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SpaceshipTuning {
+    pub health: f32,
+    pub speed: f32,
+    pub acceleration: f32,
+    pub rotation_speed: f32,
+    /// How fast the spaceship rolls, in radians a second.
+    pub roll_speed: f32,
+    pub collision_damage: f32,
+    pub shield_energy: f32,
+    pub shield_drain_per_second: f32,
+    pub shield_recharge_per_second: f32,
+    /// How fast the spaceship heals itself, once it's gone a while without taking damage.  It never heals past full
+    /// health this way.
+    pub regeneration_per_second: f32,
+    pub regeneration_delay_seconds: f32,
+    /// How many spaceships the player gets, including the first one.
+    pub lives: u32,
+    pub respawn_delay_seconds: f32,
+    /// How far from the starting position asteroids have to be before the spaceship respawns.
+    pub respawn_clear_radius: f32,
+    pub invulnerable_seconds: f32,
+}
+
+impl Default for SpaceshipTuning {
+    fn default() -> Self {
+        Self {
+            health: SPACESHIP_HEALTH,
+            speed: SPACESHIP_SPEED,
+            acceleration: SPACESHIP_ACCELERATION,
+            rotation_speed: SPACESHIP_ROTATION_SPEED,
+            roll_speed: SPACESHIP_ROLL_SPEED,
+            collision_damage: 20.0,
+            shield_energy: 100.0,
+            shield_drain_per_second: 20.0,
+            shield_recharge_per_second: 10.0,
+            regeneration_per_second: 2.0,
+            regeneration_delay_seconds: 5.0,
+            lives: 3,
+            respawn_delay_seconds: 2.0,
+            respawn_clear_radius: 25.0,
+            invulnerable_seconds: 3.0,
+        }
+    }
+}
 
 /// Marker component for the player's spaceship.
 #[derive(Component, Debug)]
 pub struct Spaceship;
 
-/// Marker component for spaceship projectiles.
+/// Marker component for projectiles fired by the spaceship.
 #[derive(Component, Debug)]
 pub struct SpaceshipMissile;
 
-/// Marker component for the spaceship's shield.
-#[derive(Component, Debug)]
-pub struct SpaceshipShield;
+/// How many spaceships the player has left, counting the one in play.
+#[derive(Resource, Debug, Default)]
+pub struct Lives {
+    pub remaining: u32,
+    /// Counts down to the next spaceship while the player is between lives.
+    respawn: Option<Timer>,
+}
+
+impl Lives {
+    /// Whether a spaceship has been lost, and the next one is waiting to come in.
+    pub fn is_respawning(&self) -> bool {
+        self.respawn.is_some()
+    }
+}
 
 /// Cooldown timer for the spaceship's weapon.
 #[derive(Resource, Debug)]
@@ -49,31 +116,61 @@ pub struct SpaceshipPlugin;
 
 impl Plugin for SpaceshipPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(SpaceshipWeaponTimer {
-            timer: Timer::from_seconds(1.0 / WEAPON_FIRE_RATE, TimerMode::Repeating),
-        })
-        .add_systems(
-            OnTransition {
-                from: GameState::Start,
-                to: GameState::InGame,
-            },
-            spawn_spaceship,
-        )
-        .add_systems(Update, game_over_when_spaceship_byebye)
-        .add_systems(
-            Update,
-            (
-                spaceship_movement_controls,
-                spaceship_weapon_controls,
-                spaceship_shield_controls,
+        app.init_resource::<SpaceshipTuning>()
+            .init_resource::<Lives>()
+            .insert_resource(SpaceshipWeaponTimer {
+                timer: Timer::new(
+                    WeaponTuning::default().single_shot.cooldown(),
+                    TimerMode::Repeating,
+                ),
+            })
+            .add_systems(
+                OnTransition {
+                    from: GameState::Start,
+                    to: GameState::InGame,
+                },
+                (spawn_spaceship, reset_weapon_timer, reset_lives),
             )
-                .chain()
-                .in_set(InGameSet::UserInput),
-        );
+            .add_systems(
+                FixedUpdate,
+                lose_life_when_spaceship_byebye.in_set(DeathReaderSet),
+            )
+            .add_systems(
+                FixedUpdate,
+                respawn_spaceship.in_set(InGameSet::EntityUpdates),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    spaceship_movement_controls,
+                    spaceship_weapon_selection,
+                    spaceship_weapon_controls,
+                    spaceship_shield_controls,
+                    spaceship_hyperspace_controls,
+                )
+                    .chain()
+                    .in_set(InGameSet::UserInput),
+            )
+            .add_systems(Update, show_spaceship);
     }
 }
 
-fn spawn_spaceship(mut commands: Commands, asset_server: Res<SceneAssets>) {
+fn spawn_spaceship(
+    mut commands: Commands,
+    asset_server: Res<SceneAssets>,
+    tuning: Res<SpaceshipTuning>,
+    weapons: Res<WeaponTuning>,
+) {
+    spawn_spaceship_at_start(&mut commands, &asset_server, &tuning, &weapons);
+}
+
+/// Spawn a fresh spaceship at the starting position, with the basic weapon and a full shield.
+fn spawn_spaceship_at_start(
+    commands: &mut Commands,
+    asset_server: &SceneAssets,
+    tuning: &SpaceshipTuning,
+    weapons: &WeaponTuning,
+) -> Entity {
     commands
         .spawn((
             MovingObjectBundle::new(
@@ -83,18 +180,42 @@ fn spawn_spaceship(mut commands: Commands, asset_server: Res<SceneAssets>) {
                     ..Default::default()
                 },
                 Collider::cuboid(4.0, 1.0, 5.0),
+                Faction::Player,
                 Velocity::new(Vec3::ZERO),
                 Acceleration::new(Vec3::ZERO),
                 AngularVelocity::new(Vec3::ZERO),
             ),
             Spaceship,
-            CollisionDamage::new(20.0),
-            Health::new(SPACESHIP_HEALTH),
+            CollisionDamage::new(tuning.collision_damage),
+            Health::new(tuning.health),
+            Regeneration::new(
+                tuning.regeneration_per_second,
+                tuning.regeneration_delay_seconds,
+            ),
+            Shield::new(
+                tuning.shield_energy,
+                tuning.shield_drain_per_second,
+                tuning.shield_recharge_per_second,
+            ),
+            Arsenal::new(WeaponKind::SingleShot),
+            HyperspaceDrive::charged(),
+            ActivePowerUps::default(),
+            weapons.single_shot.clone(),
             ActiveEvents::COLLISION_EVENTS,
             DespawnOnDie,
             ConfinedToPlayArea,
         ))
         .with_children(|builder| {
+            builder.spawn((
+                PbrBundle {
+                    mesh: asset_server.shield_bubble.clone(),
+                    material: asset_server.shield_material.clone(),
+                    transform: Transform::from_scale(Vec3::splat(SHIELD_RADIUS)),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                ShieldBubble,
+            ));
             builder.spawn(PointLightBundle {
                 transform: Transform::from_xyz(0.0, 2.0, 2.0).with_scale(Vec3::new(5.0, 5.0, 5.0)),
                 point_light: PointLight {
@@ -105,75 +226,120 @@ fn spawn_spaceship(mut commands: Commands, asset_server: Res<SceneAssets>) {
                 },
                 ..default()
             });
-        });
+        })
+        .id()
+}
+
+fn reset_weapon_timer(mut timer: ResMut<SpaceshipWeaponTimer>) {
+    timer.timer.reset();
+}
+
+fn reset_lives(mut lives: ResMut<Lives>, tuning: Res<SpaceshipTuning>) {
+    *lives = Lives {
+        remaining: tuning.lives,
+        respawn: None,
+    };
 }
 
 fn spaceship_movement_controls(
-    mut query: Query<(&mut Transform, &mut Velocity), With<Spaceship>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut query: Query<
+        (&mut Transform, &mut Velocity, &mut AngularVelocity),
+        (With<Spaceship>, Without<HyperspaceJump>),
+    >,
+    actions: Res<TickActions>,
+    tuning: Res<SpaceshipTuning>,
     time: Res<Time>,
 ) {
-    // Return early if the spaceship doesn't exist.
-    let (mut transform, mut velocity) = match query.get_single_mut() {
+    // Return early if the spaceship doesn't exist, which it doesn't while it's waiting to respawn.
+    let (mut transform, mut velocity, mut angular_velocity) = match query.get_single_mut() {
         Ok(spaceship) => spaceship,
-        Err(not_single_error) => {
-            let reason = match not_single_error {
-                QuerySingleError::NoEntities(_) => "there's no spaceship!",
-                QuerySingleError::MultipleEntities(_) => "there's more than one spaceship!",
-            };
-
-            warn!("Can't do spaceship movement, {reason}");
+        Err(QuerySingleError::NoEntities(_)) => return,
+        Err(QuerySingleError::MultipleEntities(_)) => {
+            warn!("Can't do spaceship movement, there's more than one spaceship!");
             return;
         }
     };
 
-    let left_input = [KeyCode::KeyA, KeyCode::ArrowLeft];
-    let right_input = [KeyCode::KeyD, KeyCode::ArrowRight];
-    let forward_input = [KeyCode::KeyW, KeyCode::ArrowUp];
-
     // Handle rotation of the spaceship.
-    let mut rotation = 0.0;
-    if keyboard_input.any_pressed(right_input) {
-        rotation = -SPACESHIP_ROTATION_SPEED * time.delta_seconds();
-    } else if keyboard_input.any_pressed(left_input) {
-        rotation = SPACESHIP_ROTATION_SPEED * time.delta_seconds();
-    }
-    transform.rotate_y(rotation);
+    // Analog input turns the spaceship more slowly the less it's pushed.
+    let turn = actions.value(InputAction::RotateLeft) - actions.value(InputAction::RotateRight);
+    transform.rotate_y(turn * tuning.rotation_speed * time.delta_seconds());
 
     // Handle roll of the spaceship.
     let mut roll = 0.0;
-    if keyboard_input.pressed(KeyCode::ShiftLeft) {
-        roll = -SPACESHIP_ROLL_SPEED;
-    } else if keyboard_input.pressed(KeyCode::ControlLeft) {
-        roll = SPACESHIP_ROLL_SPEED;
+    if actions.pressed(InputAction::RollLeft) {
+        roll = -tuning.roll_speed * time.delta_seconds();
+    } else if actions.pressed(InputAction::RollRight) {
+        roll = tuning.roll_speed * time.delta_seconds();
     }
     transform.rotate_local_z(roll);
 
     // The spaceship slows down over time.
     velocity.value = velocity.value - velocity.value * (0.5 * time.delta_seconds());
+    // So does any spin it's been knocked into.
+    angular_velocity.value =
+        angular_velocity.value - angular_velocity.value * (2.0 * time.delta_seconds());
+
+    if actions.pressed(InputAction::Thrust) {
+        // If the speed in the direction we are facing is less than the top speed, accelerate in that direction.
+        if -transform.forward().dot(velocity.value) <= tuning.speed {
+            velocity.value += -transform.forward()
+                * tuning.acceleration
+                * actions.value(InputAction::Thrust)
+                * time.delta_seconds();
+        }
+    }
+}
+
+/// Keep the spaceship's weapon up to date with what's unlocked, selected and tuned.
+fn spaceship_weapon_selection(
+    mut query: Query<(&mut Arsenal, &mut Weapon), With<Spaceship>>,
+    actions: Res<TickActions>,
+    tuning: Res<WeaponTuning>,
+    scoreboard: Res<Scoreboard>,
+) {
+    let Ok((mut arsenal, mut weapon)) = query.get_single_mut() else {
+        return;
+    };
 
-    if keyboard_input.any_pressed(forward_input) {
-        // If the speed in the direction we are facing is less than SPACESHIP_SPEED, accelerate in that direction.
-        if -transform.forward().dot(velocity.value) <= SPACESHIP_SPEED {
-            velocity.value += -transform.forward() * SPACESHIP_ACCELERATION;
+    // Better weapons unlock as the score goes up.
+    for kind in WeaponKind::ALL {
+        if scoreboard.score >= tuning.get(kind).unlock_score && arsenal.unlock(kind) {
+            info!("Unlocked the {}", kind.name());
         }
     }
+
+    if actions.just_pressed(InputAction::CycleWeapon) {
+        arsenal.cycle();
+    }
+
+    if arsenal.is_changed() || tuning.is_changed() {
+        weapon.set_if_neq(tuning.get(arsenal.selected()).clone());
+    }
 }
 
 fn spaceship_weapon_controls(
     mut commands: Commands,
-    query: Query<&Transform, With<Spaceship>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    query: Query<
+        (&Transform, &Weapon, &ActivePowerUps),
+        (With<Spaceship>, Without<HyperspaceJump>),
+    >,
+    actions: Res<TickActions>,
     asset_server: Res<SceneAssets>,
+    power_up_tuning: Res<PowerUpTuning>,
     time: Res<Time>,
     mut timer: ResMut<SpaceshipWeaponTimer>,
 ) {
-    let Ok(transform) = query.get_single() else {
+    let Ok((transform, weapon, power_ups)) = query.get_single() else {
         return;
     };
 
+    // Rapid fire and multishot come and go, so the weapon is worked out fresh every tick.
+    let weapon = power_ups.boost(weapon, &power_up_tuning);
+    timer.timer.set_duration(weapon.cooldown());
+
     // Press space to shoot. Pew pew.
-    if !keyboard_input.pressed(KeyCode::Space) {
+    if !actions.pressed(InputAction::Fire) {
         return;
     }
 
@@ -183,53 +349,135 @@ fn spaceship_weapon_controls(
         return;
     }
 
-    commands.spawn((
-        MovingObjectBundle::new(
-            SceneBundle {
-                scene: asset_server.missiles.clone(),
-                transform: Transform::from_translation(
-                    transform.translation + -transform.forward() * MISSILE_FORWARD_SCALAR,
-                )
-                .with_rotation(transform.rotation),
-                ..Default::default()
-            },
-            Collider::ball(0.5),
-            Velocity::new(-transform.forward() * MISSILE_SPEED),
-            Acceleration::new(Vec3::ZERO),
-            AngularVelocity::new(Vec3::ZERO),
-        ),
-        SpaceshipMissile,
-        CollisionDamage::new(5.0),
-        Health::new(2.5),
-        DespawnOnDie,
-        ConfinedToPlayArea,
-        DespawnTimer::new(Duration::from_millis(MISSILE_LIFESPAN_MILLIS))
-    ));
+    for projectile in fire_weapon(
+        &mut commands,
+        &asset_server,
+        transform,
+        &weapon,
+        Faction::PlayerProjectile,
+    ) {
+        commands.entity(projectile).insert(SpaceshipMissile);
+    }
 }
 
 fn spaceship_shield_controls(
+    mut query: Query<(&mut Shield, Has<HyperspaceJump>), With<Spaceship>>,
+    actions: Res<TickActions>,
+    tuning: Res<SpaceshipTuning>,
+) {
+    let Ok((mut shield, jumping)) = query.get_single_mut() else {
+        return;
+    };
+
+    if tuning.is_changed() {
+        shield.max_energy = tuning.shield_energy;
+        shield.energy = shield.energy.min(tuning.shield_energy);
+        shield.drain_per_second = tuning.shield_drain_per_second;
+        shield.recharge_per_second = tuning.shield_recharge_per_second;
+    }
+
+    // Hold tab to keep the shield up.  It can't be raised while jumping through hyperspace.
+    shield.hold(actions.pressed(InputAction::Shield) && !jumping);
+}
+
+fn spaceship_hyperspace_controls(
     mut commands: Commands,
-    query: Query<Entity, With<Spaceship>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut query: Query<
+        (Entity, &mut HyperspaceDrive, &mut Velocity),
+        (With<Spaceship>, Without<HyperspaceJump>),
+    >,
+    actions: Res<TickActions>,
+    tuning: Res<HyperspaceTuning>,
 ) {
-    let Ok(spaceship) = query.get_single() else {
+    let Ok((entity, mut drive, mut velocity)) = query.get_single_mut() else {
         return;
     };
 
-    if keyboard_input.pressed(KeyCode::Tab) {
-        commands.entity(spaceship).insert(SpaceshipShield);
+    // Press S to escape through hyperspace.
+    if actions.just_pressed(InputAction::Hyperspace) {
+        jump_to_hyperspace(&mut commands, entity, &mut drive, &mut velocity, &tuning);
     }
 }
 
-fn game_over_when_spaceship_byebye(
+fn lose_life_when_spaceship_byebye(
     query: Query<Entity, With<Spaceship>>,
     mut event_reader: EventReader<DieEvent>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut lives: ResMut<Lives>,
+    tuning: Res<SpaceshipTuning>,
 ) {
-    // Check each die event to see if it's the spaceship, then set gameover if it is.
-    for DieEvent { entity } in event_reader.read() {
-        if query.get(*entity).is_ok() {
+    // Check each die event to see if it's the spaceship, then set gameover if it was the last one.
+    for DieEvent { entity, .. } in event_reader.read() {
+        if query.get(*entity).is_err() {
+            continue;
+        }
+
+        lives.remaining = lives.remaining.saturating_sub(1);
+        if lives.remaining == 0 {
             next_state.set(GameState::GameOver)
+        } else {
+            lives.respawn = Some(Timer::from_seconds(
+                tuning.respawn_delay_seconds.max(0.0),
+                TimerMode::Once,
+            ));
         }
     }
-}
\ No newline at end of file
+}
+
+/// Bring in the next spaceship once the respawn delay is up and there are no asteroids near the starting position.
+/// It can't be hurt for a few seconds after it comes in.
+fn respawn_spaceship(
+    mut commands: Commands,
+    mut lives: ResMut<Lives>,
+    asteroids: Query<&Transform, With<Asteroid>>,
+    asset_server: Res<SceneAssets>,
+    tuning: Res<SpaceshipTuning>,
+    weapons: Res<WeaponTuning>,
+    time: Res<Time>,
+) {
+    let Some(timer) = lives.respawn.as_mut() else {
+        return;
+    };
+    if !timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    let clear = asteroids.iter().all(|transform| {
+        transform.translation.distance(STARTING_TRANSLATION) > tuning.respawn_clear_radius
+    });
+    if !clear {
+        return;
+    }
+
+    lives.respawn = None;
+    let spaceship = spawn_spaceship_at_start(&mut commands, &asset_server, &tuning, &weapons);
+    commands
+        .entity(spaceship)
+        .insert(Invulnerable::from_seconds(tuning.invulnerable_seconds));
+}
+
+/// Hide the spaceship while it's away in hyperspace, and blink it on and off while it's invulnerable.
+fn show_spaceship(
+    mut query: Query<
+        (
+            &mut Visibility,
+            Option<&Invulnerable>,
+            Option<&HyperspaceJump>,
+        ),
+        With<Spaceship>,
+    >,
+) {
+    for (mut visibility, invulnerable, jump) in query.iter_mut() {
+        let away = jump.is_some_and(|jump| jump.phase() == HyperspacePhase::Away);
+        let blinked_off = invulnerable.is_some_and(|invulnerable| {
+            (invulnerable.timer.elapsed_secs() / BLINK_SECONDS) as u32 % 2 == 1
+        });
+        let hidden = away || blinked_off;
+
+        visibility.set_if_neq(if hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+}
@@ -1,8 +1,6 @@
 use bevy::prelude::*;
 
-const PAUSE_BUTTON: KeyCode = KeyCode::Escape;
-const CONTINUE_BUTTON: KeyCode = KeyCode::Space;
-const QUIT_BUTTON: KeyCode = KeyCode::KeyQ;
+use crate::input::{ActionState, InputAction};
 
 #[derive(Debug, Default, States, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum GameState {
@@ -13,6 +11,12 @@ pub enum GameState {
     GameOver,
 }
 
+/// Whether the game is being played, and isn't about to stop.  Once something pauses or ends the game part way
+/// through a frame, the rest of the frame's ticks are skipped so games play out the same at any frame rate.
+pub fn playing(state: Res<State<GameState>>, next_state: Res<NextState<GameState>>) -> bool {
+    *state.get() == GameState::InGame && next_state.0.is_none()
+}
+
 pub struct GameStatePlugin;
 
 impl Plugin for GameStatePlugin {
@@ -25,11 +29,11 @@ impl Plugin for GameStatePlugin {
 fn game_state_input_events(
     mut next_state: ResMut<NextState<GameState>>,
     state: Res<State<GameState>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     mut app_exit_events: ResMut<Events<bevy::app::AppExit>>
 ) {
     // Pause/Unpause the game.
-    if keyboard_input.just_pressed(PAUSE_BUTTON) {
+    if actions.just_pressed(InputAction::Pause) {
         match state.get() {
             GameState::InGame => next_state.set(GameState::Paused),
             GameState::Paused => next_state.set(GameState::InGame),
@@ -38,7 +42,7 @@ fn game_state_input_events(
     }
 
     // Continue from the start and game over screen.
-    if keyboard_input.just_pressed(CONTINUE_BUTTON) {
+    if actions.just_pressed(InputAction::Confirm) {
         match state.get() {
             GameState::Start => next_state.set(GameState::InGame),
             GameState::GameOver => next_state.set(GameState::Start),
@@ -47,7 +51,7 @@ fn game_state_input_events(
     }
 
     // Quit the current game or the program.
-    if keyboard_input.just_pressed(QUIT_BUTTON) {
+    if actions.just_pressed(InputAction::Quit) {
         match state.get() {
             GameState::Start => {app_exit_events.send(bevy::app::AppExit);},
             GameState::Paused => next_state.set(GameState::GameOver),
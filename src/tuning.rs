@@ -0,0 +1,552 @@
+use std::{fmt, fs, io, ops::Range};
+
+use bevy::asset::{io::file::FileAssetReader, io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asteroids::AsteroidTuning, collision::CollisionTuning, director::DirectorTuning,
+    enemy::SaucerTuning, hyperspace::HyperspaceTuning, movement::MovementTuning,
+    powerup::PowerUpTuning, spaceship::SpaceshipTuning, wave::WaveTuning, weapon::WeaponTuning,
+};
+
+/// Where the tuning file lives, inside the assets folder.
+pub const TUNING_PATH: &str = "tuning.ron";
+/// The longest any of the tuning's timers can be.  Much longer and they can't be turned into a [std::time::Duration].
+const MAX_SECONDS: f32 = u32::MAX as f32;
+/// The same for the timers measured in milliseconds.
+const MAX_MILLIS: u64 = u32::MAX as u64 * 1000;
+/// The most fragments an asteroid can split into.
+const MAX_FRAGMENTS: u32 = 32;
+/// The most asteroids the director can have in play at once.
+const MAX_ASTEROIDS: u32 = 1000;
+/// The most projectiles a weapon can fire in one shot, multishot included.
+const MAX_PROJECTILES: u32 = 64;
+
+/// Loads the gameplay balance numbers from `assets/tuning.ron` into the [SpaceshipTuning], [WeaponTuning],
+/// [HyperspaceTuning], [AsteroidTuning], [WaveTuning], [DirectorTuning], [SaucerTuning], [PowerUpTuning],
+/// [CollisionTuning] and [MovementTuning] resources.  Anything left out of the file keeps its default, and a file
+/// with numbers the game can't use is turned away, see [Tuning::validate].
+#[derive(Default)]
+pub struct TuningPlugin {
+    pub source: TuningSource,
+}
+
+/// Where the [TuningPlugin] gets its numbers from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TuningSource {
+    /// Stick to [Tuning::default] and never read the file, so editing it can't change what the tests do.
+    Defaults,
+    /// Read the file once at startup, and keep those numbers however the file is edited afterwards.
+    File,
+    /// Read the file, then watch it through the asset server and re-apply it whenever it's edited.
+    #[default]
+    HotReloadedFile,
+}
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        if self.source == TuningSource::Defaults {
+            Tuning::default().insert_into(&mut app.world);
+            return;
+        }
+
+        // Read the file straight away so the very first tick is already tuned.
+        let tuning = match Tuning::read() {
+            Ok(tuning) => tuning,
+            Err(TuningError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {
+                Tuning::default()
+            }
+            Err(error) => {
+                warn!("Using the default tuning, {error}");
+                Tuning::default()
+            }
+        };
+        tuning.insert_into(&mut app.world);
+
+        if self.source == TuningSource::HotReloadedFile {
+            app.init_asset::<Tuning>()
+                .init_asset_loader::<TuningLoader>()
+                .add_systems(Startup, load_tuning)
+                .add_systems(Update, apply_tuning);
+        }
+    }
+}
+
+/// All of the gameplay balance numbers.
+#[derive(Asset, TypePath, Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Tuning {
+    pub spaceship: SpaceshipTuning,
+    pub weapons: WeaponTuning,
+    pub hyperspace: HyperspaceTuning,
+    pub asteroids: AsteroidTuning,
+    pub waves: WaveTuning,
+    pub director: DirectorTuning,
+    pub saucers: SaucerTuning,
+    pub power_ups: PowerUpTuning,
+    pub collisions: CollisionTuning,
+    pub movement: MovementTuning,
+}
+
+impl Tuning {
+    /// Read the tuning file from the assets folder, without going through the asset server.
+    pub fn read() -> Result<Self, TuningError> {
+        let path = FileAssetReader::get_base_path()
+            .join("assets")
+            .join(TUNING_PATH);
+        let contents = fs::read_to_string(path)?;
+        let tuning: Tuning = ron::from_str(&contents)?;
+        tuning.validate()?;
+        Ok(tuning)
+    }
+
+    /// Check every number is one the game can use, so a bad edit to the file is turned away instead of crashing the
+    /// game.
+    pub fn validate(&self) -> Result<(), TuningError> {
+        let seconds = [
+            (
+                "spaceship.regeneration_delay_seconds",
+                self.spaceship.regeneration_delay_seconds,
+            ),
+            (
+                "spaceship.respawn_delay_seconds",
+                self.spaceship.respawn_delay_seconds,
+            ),
+            (
+                "spaceship.invulnerable_seconds",
+                self.spaceship.invulnerable_seconds,
+            ),
+            (
+                "hyperspace.cooldown_seconds",
+                self.hyperspace.cooldown_seconds,
+            ),
+            ("hyperspace.jump_seconds", self.hyperspace.jump_seconds),
+            (
+                "hyperspace.reentry_seconds",
+                self.hyperspace.reentry_seconds,
+            ),
+            (
+                "asteroids.spawn_time_seconds",
+                self.asteroids.spawn_time_seconds,
+            ),
+            ("waves.banner_seconds", self.waves.banner_seconds),
+            (
+                "director.damage_memory_seconds",
+                self.director.damage_memory_seconds,
+            ),
+            (
+                "director.kill_rate_seconds",
+                self.director.kill_rate_seconds,
+            ),
+            ("saucers.spawn_seconds", self.saucers.spawn_seconds),
+            ("saucers.turn_seconds", self.saucers.turn_seconds),
+            (
+                "saucers.large.fire_seconds",
+                self.saucers.large.fire_seconds,
+            ),
+            (
+                "saucers.small.fire_seconds",
+                self.saucers.small.fire_seconds,
+            ),
+            (
+                "power_ups.lifespan_seconds",
+                self.power_ups.lifespan_seconds,
+            ),
+            ("power_ups.effect_seconds", self.power_ups.effect_seconds),
+        ];
+        let amounts = [
+            (
+                "weapons.single_shot.fire_rate",
+                self.weapons.single_shot.fire_rate,
+            ),
+            (
+                "weapons.triple_spread.fire_rate",
+                self.weapons.triple_spread.fire_rate,
+            ),
+            (
+                "weapons.rapid_fire.fire_rate",
+                self.weapons.rapid_fire.fire_rate,
+            ),
+            (
+                "weapons.piercing_beam.fire_rate",
+                self.weapons.piercing_beam.fire_rate,
+            ),
+            (
+                "weapons.homing_missile.fire_rate",
+                self.weapons.homing_missile.fire_rate,
+            ),
+            (
+                "power_ups.rapid_fire_multiplier",
+                self.power_ups.rapid_fire_multiplier,
+            ),
+            (
+                "weapons.single_shot.projectile.speed",
+                self.weapons.single_shot.projectile.speed,
+            ),
+            (
+                "weapons.triple_spread.projectile.speed",
+                self.weapons.triple_spread.projectile.speed,
+            ),
+            (
+                "weapons.rapid_fire.projectile.speed",
+                self.weapons.rapid_fire.projectile.speed,
+            ),
+            (
+                "weapons.piercing_beam.projectile.speed",
+                self.weapons.piercing_beam.projectile.speed,
+            ),
+            (
+                "weapons.homing_missile.projectile.speed",
+                self.weapons.homing_missile.projectile.speed,
+            ),
+            ("saucers.projectile.speed", self.saucers.projectile.speed),
+        ];
+        for (field, value) in seconds {
+            if !(0.0..=MAX_SECONDS).contains(&value) {
+                return Err(TuningError::Invalid {
+                    field,
+                    requirement: "a number of seconds, from 0 to 4294967295",
+                });
+            }
+        }
+        for (field, value) in amounts {
+            if !value.is_finite() || value < 0.0 {
+                return Err(TuningError::Invalid {
+                    field,
+                    requirement: "a number, zero or more",
+                });
+            }
+        }
+
+        let chances = [
+            (
+                "hyperspace.malfunction_chance",
+                self.hyperspace.malfunction_chance,
+            ),
+            ("saucers.small_chance", self.saucers.small_chance),
+            ("power_ups.drop_chance", self.power_ups.drop_chance),
+        ];
+        for (field, value) in chances {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(TuningError::Invalid {
+                    field,
+                    requirement: "from 0.0 to 1.0",
+                });
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.collisions.restitution) {
+            return Err(TuningError::Invalid {
+                field: "collisions.restitution",
+                requirement: "from 0.0 to 1.0",
+            });
+        }
+
+        let Range { start, end } = self.asteroids.health_range;
+        if !end.is_finite() || !(0.0..=end).contains(&start) {
+            return Err(TuningError::Invalid {
+                field: "asteroids.health_range",
+                requirement: "a range of numbers, zero or more, that doesn't go backwards",
+            });
+        }
+
+        // Fragments with as much health as their parent would go on splitting forever.
+        if !(0.0..1.0).contains(&self.asteroids.fragment_health_fraction) {
+            return Err(TuningError::Invalid {
+                field: "asteroids.fragment_health_fraction",
+                requirement: "from 0.0 up to, but not including, 1.0",
+            });
+        }
+
+        let lifespans = [
+            (
+                "weapons.single_shot.projectile.lifespan_millis",
+                self.weapons.single_shot.projectile.lifespan_millis,
+            ),
+            (
+                "weapons.triple_spread.projectile.lifespan_millis",
+                self.weapons.triple_spread.projectile.lifespan_millis,
+            ),
+            (
+                "weapons.rapid_fire.projectile.lifespan_millis",
+                self.weapons.rapid_fire.projectile.lifespan_millis,
+            ),
+            (
+                "weapons.piercing_beam.projectile.lifespan_millis",
+                self.weapons.piercing_beam.projectile.lifespan_millis,
+            ),
+            (
+                "weapons.homing_missile.projectile.lifespan_millis",
+                self.weapons.homing_missile.projectile.lifespan_millis,
+            ),
+            (
+                "saucers.projectile.lifespan_millis",
+                self.saucers.projectile.lifespan_millis,
+            ),
+        ];
+        for (field, value) in lifespans {
+            if !(1..=MAX_MILLIS).contains(&value) {
+                return Err(TuningError::Invalid {
+                    field,
+                    requirement: "a number of milliseconds, from 1 to 4294967295000",
+                });
+            }
+        }
+
+        // Multishot adds to every weapon's projectiles, so there has to be room for them.
+        let projectile_counts = [
+            (
+                "weapons.single_shot.projectile_count",
+                self.weapons.single_shot.projectile_count,
+            ),
+            (
+                "weapons.triple_spread.projectile_count",
+                self.weapons.triple_spread.projectile_count,
+            ),
+            (
+                "weapons.rapid_fire.projectile_count",
+                self.weapons.rapid_fire.projectile_count,
+            ),
+            (
+                "weapons.piercing_beam.projectile_count",
+                self.weapons.piercing_beam.projectile_count,
+            ),
+            (
+                "weapons.homing_missile.projectile_count",
+                self.weapons.homing_missile.projectile_count,
+            ),
+        ];
+        let extra_projectiles = self.power_ups.multishot_extra_projectiles;
+        for (field, value) in projectile_counts {
+            if value.max(1).saturating_add(extra_projectiles) > MAX_PROJECTILES {
+                return Err(TuningError::Invalid {
+                    field,
+                    requirement:
+                        "a whole number up to 64, counting power_ups.multishot_extra_projectiles",
+                });
+            }
+        }
+
+        let AsteroidTuning {
+            min_fragments,
+            max_fragments,
+            ..
+        } = self.asteroids;
+        if min_fragments > max_fragments || max_fragments > MAX_FRAGMENTS {
+            return Err(TuningError::Invalid {
+                field: "asteroids.max_fragments",
+                requirement: "a whole number up to 32, and no less than asteroids.min_fragments",
+            });
+        }
+
+        // Without room for a single asteroid, a wave would never end.
+        if !(1..=MAX_ASTEROIDS).contains(&self.director.max_asteroids) {
+            return Err(TuningError::Invalid {
+                field: "director.max_asteroids",
+                requirement: "a whole number from 1 to 1000",
+            });
+        }
+
+        let curve = &self.director.intensity_curve;
+        let bad_point = curve.iter().any(|&(seconds, intensity)| {
+            !(0.0..=MAX_SECONDS).contains(&seconds) || !(0.0..=1.0).contains(&intensity)
+        });
+        let backwards = curve.windows(2).any(|points| points[1].0 < points[0].0);
+        if curve.is_empty() || bad_point || backwards {
+            return Err(TuningError::Invalid {
+                field: "director.intensity_curve",
+                requirement:
+                    "at least one point, in order of seconds, with intensities from 0.0 to 1.0",
+            });
+        }
+
+        let types = &self.asteroids.types;
+        let total_weight: f32 = types.iter().map(|asteroid_type| asteroid_type.weight).sum();
+        let bad_weight = types
+            .iter()
+            .any(|asteroid_type| !(0.0..=f32::MAX).contains(&asteroid_type.weight));
+        if bad_weight || !total_weight.is_finite() || (total_weight <= 0.0 && !types.is_empty()) {
+            return Err(TuningError::Invalid {
+                field: "asteroids.types.weight",
+                requirement: "a number, zero or more, with at least one type's above zero",
+            });
+        }
+
+        let world_size = self.movement.world_size;
+        if !world_size.is_finite() || world_size <= 0.0 {
+            return Err(TuningError::Invalid {
+                field: "movement.world_size",
+                requirement: "a number above zero",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Put the numbers into the resources the game reads.
+    pub fn insert_into(self, world: &mut World) {
+        world.insert_resource(self.spaceship);
+        world.insert_resource(self.weapons);
+        world.insert_resource(self.hyperspace);
+        world.insert_resource(self.asteroids);
+        world.insert_resource(self.waves);
+        world.insert_resource(self.director);
+        world.insert_resource(self.saucers);
+        world.insert_resource(self.power_ups);
+        world.insert_resource(self.collisions);
+        world.insert_resource(self.movement);
+    }
+}
+
+/// The resources the game reads its tuning from, for systems that need all of them at once.
+#[derive(SystemParam)]
+pub struct TuningResources<'w> {
+    spaceship: ResMut<'w, SpaceshipTuning>,
+    weapons: ResMut<'w, WeaponTuning>,
+    hyperspace: ResMut<'w, HyperspaceTuning>,
+    asteroids: ResMut<'w, AsteroidTuning>,
+    waves: ResMut<'w, WaveTuning>,
+    director: ResMut<'w, DirectorTuning>,
+    saucers: ResMut<'w, SaucerTuning>,
+    power_ups: ResMut<'w, PowerUpTuning>,
+    collisions: ResMut<'w, CollisionTuning>,
+    movement: ResMut<'w, MovementTuning>,
+}
+
+impl TuningResources<'_> {
+    /// The tuning the game is using right now.
+    pub fn current(&self) -> Tuning {
+        Tuning {
+            spaceship: self.spaceship.clone(),
+            weapons: self.weapons.clone(),
+            hyperspace: self.hyperspace.clone(),
+            asteroids: self.asteroids.clone(),
+            waves: self.waves.clone(),
+            director: self.director.clone(),
+            saucers: self.saucers.clone(),
+            power_ups: self.power_ups.clone(),
+            collisions: self.collisions.clone(),
+            movement: self.movement.clone(),
+        }
+    }
+
+    /// Switch to new tuning.  Only the resources whose numbers differ are marked as changed.
+    pub fn apply(&mut self, tuning: &Tuning) {
+        self.spaceship.set_if_neq(tuning.spaceship.clone());
+        self.weapons.set_if_neq(tuning.weapons.clone());
+        self.hyperspace.set_if_neq(tuning.hyperspace.clone());
+        self.asteroids.set_if_neq(tuning.asteroids.clone());
+        self.waves.set_if_neq(tuning.waves.clone());
+        self.director.set_if_neq(tuning.director.clone());
+        self.saucers.set_if_neq(tuning.saucers.clone());
+        self.power_ups.set_if_neq(tuning.power_ups.clone());
+        self.collisions.set_if_neq(tuning.collisions.clone());
+        self.movement.set_if_neq(tuning.movement.clone());
+    }
+}
+
+/// Why the tuning file couldn't be used.
+#[derive(Debug)]
+pub enum TuningError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    /// A number the game can't use, like a negative duration.
+    Invalid {
+        field: &'static str,
+        requirement: &'static str,
+    },
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningError::Io(error) => write!(f, "couldn't read the tuning file: {error}"),
+            TuningError::Parse(error) => write!(f, "couldn't parse the tuning file: {error}"),
+            TuningError::Invalid { field, requirement } => {
+                write!(f, "the tuning file's {field} has to be {requirement}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TuningError {}
+
+impl From<io::Error> for TuningError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for TuningError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+#[derive(Default)]
+struct TuningLoader;
+
+impl AssetLoader for TuningLoader {
+    type Asset = Tuning;
+    type Settings = ();
+    type Error = TuningError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Tuning, TuningError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let tuning: Tuning = ron::de::from_bytes(&bytes)?;
+            // Failing the load leaves the last good tuning in place.
+            tuning.validate()?;
+            Ok(tuning)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning.ron"]
+    }
+}
+
+/// Keeps the tuning file loaded so the asset server watches it for changes.
+#[derive(Resource, Debug)]
+struct TuningHandle(Handle<Tuning>);
+
+fn load_tuning(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TuningHandle(asset_server.load(TUNING_PATH)));
+}
+
+/// Copy the tuning into the resources the game reads whenever the file is loaded or edited.
+fn apply_tuning(
+    mut events: EventReader<AssetEvent<Tuning>>,
+    handle: Option<Res<TuningHandle>>,
+    assets: Res<Assets<Tuning>>,
+    mut resources: TuningResources,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != handle.0.id() {
+            continue;
+        }
+        let Some(tuning) = assets.get(*id) else {
+            continue;
+        };
+
+        info!("Applying gameplay tuning");
+        resources.apply(tuning);
+    }
+}
@@ -1,14 +1,41 @@
 use crate::despawn::remove_with_component;
 use bevy::prelude::*;
 
-use crate::{health::Health, scoreboard::Scoreboard, spaceship::Spaceship, state::GameState};
+use crate::{
+    collision::Faction,
+    health::{Health, ResistedEvent},
+    hyperspace::HyperspaceDrive,
+    input::{InputAction, InputBindings},
+    powerup::ActivePowerUps,
+    replay::PlaybackState,
+    scoreboard::Scoreboard,
+    shield::Shield,
+    spaceship::{Lives, Spaceship},
+    state::GameState,
+    wave::Wave,
+    weapon::Weapon,
+};
 pub struct GameUiPlugin;
 
 impl Plugin for GameUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::InGame), spawn_game_ui)
             .add_systems(OnExit(GameState::InGame), remove_with_component::<GameUi>)
-            .add_systems(Update, (update_health_ui, update_score));
+            .add_systems(
+                Update,
+                (
+                    update_health_ui,
+                    update_lives_ui,
+                    update_shield_ui,
+                    update_weapon_ui,
+                    update_hyperspace_ui,
+                    update_power_up_ui,
+                    update_resisted_ui,
+                    update_score,
+                    update_replay_ui,
+                    update_wave_banner,
+                ),
+            );
     }
 }
 
@@ -18,17 +45,47 @@ struct GameUi;
 #[derive(Component)]
 struct HealthDisplay;
 
+#[derive(Component)]
+struct LivesDisplay;
+
+#[derive(Component)]
+struct ShieldDisplay;
+
+#[derive(Component)]
+struct WeaponDisplay;
+
+#[derive(Component)]
+struct HyperspaceDisplay;
+
+#[derive(Component)]
+struct PowerUpDisplay;
+
+/// Shows for a moment after armor shrugs off some of the player's damage.
+#[derive(Component)]
+struct ResistedDisplay {
+    timer: Timer,
+}
+
 #[derive(Component)]
 struct ScoreDisplay;
 
+#[derive(Component)]
+struct ReplayDisplay;
+
+#[derive(Component)]
+struct WaveBanner;
+
 fn spawn_game_ui(mut commands: Commands) {
     commands
         .spawn((
             NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
-                    height: Val::Px(40.0),
+                    min_height: Val::Px(40.0),
                     align_items: AlignItems::Center,
+                    // Too much to fit across a narrow window on one line.
+                    flex_wrap: FlexWrap::Wrap,
+                    row_gap: Val::Px(5.0),
                     padding: UiRect::all(Val::Px(10.0)),
                     ..default()
                 },
@@ -51,15 +108,107 @@ fn spawn_game_ui(mut commands: Commands) {
                 HealthDisplay,
             ));
 
-            commands.spawn(
-                NodeBundle {
-                    style: Style {
-                        width: Val::Px(25.0),
-                        ..default()
-                    },
+            commands.spawn(spacer());
+
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "Lives!",
+                        TextStyle {
+                            font_size: 32.0,
+                            ..default()
+                        },
+                    ),
                     ..default()
-                }
-            );
+                },
+                LivesDisplay,
+            ));
+
+            commands.spawn(spacer());
+
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "Shield!",
+                        TextStyle {
+                            font_size: 32.0,
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                },
+                ShieldDisplay,
+            ));
+
+            commands.spawn(spacer());
+
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "Weapon!",
+                        TextStyle {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                },
+                WeaponDisplay,
+            ));
+
+            commands.spawn(spacer());
+
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "Hyperspace!",
+                        TextStyle {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                },
+                HyperspaceDisplay,
+            ));
+
+            commands.spawn(spacer());
+
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 24.0,
+                            color: Color::rgb(1.0, 0.85, 0.2),
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                },
+                PowerUpDisplay,
+            ));
+
+            commands.spawn(spacer());
+
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 24.0,
+                            color: Color::rgb(0.7, 0.7, 0.75),
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                },
+                ResistedDisplay {
+                    timer: Timer::from_seconds(1.0, TimerMode::Once),
+                },
+            ));
+
+            commands.spawn(spacer());
 
             commands.spawn((
                 TextBundle {
@@ -74,9 +223,68 @@ fn spawn_game_ui(mut commands: Commands) {
                 },
                 ScoreDisplay,
             ));
+
+            commands.spawn(spacer());
+
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                },
+                ReplayDisplay,
+            ));
+        });
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            GameUi,
+        ))
+        .with_children(|commands| {
+            commands.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "Wave!",
+                        TextStyle {
+                            font_size: 64.0,
+                            ..default()
+                        },
+                    ),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                WaveBanner,
+            ));
         });
 }
 
+/// A gap between two of the HUD's readouts.
+fn spacer() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Px(25.0),
+            ..default()
+        },
+        ..default()
+    }
+}
+
 fn update_health_ui(
     mut texts: Query<&mut Text, With<HealthDisplay>>,
     player_health: Query<&Health, With<Spaceship>>,
@@ -86,7 +294,105 @@ fn update_health_ui(
     };
 
     for mut text in &mut texts {
-        text.sections[0].value = format!("Health: {:.1}%", (health.value/crate::spaceship::SPACESHIP_HEALTH)*100.0);
+        text.sections[0].value = format!("Health: {:.1}%", health.fraction() * 100.0);
+    }
+}
+
+fn update_lives_ui(mut texts: Query<&mut Text, With<LivesDisplay>>, lives: Res<Lives>) {
+    for mut text in &mut texts {
+        text.sections[0].value = format!("Lives: {}", lives.remaining);
+    }
+}
+
+fn update_shield_ui(
+    mut texts: Query<&mut Text, With<ShieldDisplay>>,
+    player_shield: Query<&Shield, With<Spaceship>>,
+) {
+    let Ok(shield) = player_shield.get_single() else {
+        return;
+    };
+
+    for mut text in &mut texts {
+        text.sections[0].value = format!("Shield: {:.0}%", shield.fraction() * 100.0);
+        // Light up while the shield is taking hits.
+        text.sections[0].style.color = if shield.is_up() {
+            Color::rgb(0.4, 0.8, 1.0)
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+fn update_weapon_ui(
+    mut texts: Query<&mut Text, With<WeaponDisplay>>,
+    player_weapon: Query<&Weapon, With<Spaceship>>,
+) {
+    let Ok(weapon) = player_weapon.get_single() else {
+        return;
+    };
+
+    for mut text in &mut texts {
+        text.sections[0].value = weapon.kind.name().to_string();
+    }
+}
+
+fn update_hyperspace_ui(
+    mut texts: Query<&mut Text, With<HyperspaceDisplay>>,
+    player_drive: Query<&HyperspaceDrive, With<Spaceship>>,
+) {
+    let Ok(drive) = player_drive.get_single() else {
+        return;
+    };
+
+    for mut text in &mut texts {
+        if drive.is_ready() {
+            text.sections[0].value = "Hyperspace: ready".to_string();
+            text.sections[0].style.color = Color::WHITE;
+        } else {
+            text.sections[0].value = format!("Hyperspace: {:.1}s", drive.remaining_secs());
+            text.sections[0].style.color = Color::GRAY;
+        }
+    }
+}
+
+/// List the timed power-ups the spaceship has going, with how long each has left.
+fn update_power_up_ui(
+    mut texts: Query<&mut Text, With<PowerUpDisplay>>,
+    player_power_ups: Query<&ActivePowerUps, With<Spaceship>>,
+) {
+    let Ok(power_ups) = player_power_ups.get_single() else {
+        return;
+    };
+
+    for mut text in &mut texts {
+        text.sections[0].value = power_ups
+            .remaining_secs()
+            .map(|(kind, seconds)| format!("{}: {:.1}s", kind.name(), seconds))
+            .collect::<Vec<_>>()
+            .join("  ");
+    }
+}
+
+/// Let the player know when armor has shrugged off their damage, and what kind it was.
+fn update_resisted_ui(
+    mut texts: Query<(&mut Text, &mut ResistedDisplay)>,
+    mut resisted_events: EventReader<ResistedEvent>,
+    factions: Query<&Faction>,
+    time: Res<Time>,
+) {
+    let resisted = resisted_events
+        .read()
+        .filter(|event| event.credited_to_player(&factions))
+        .last()
+        .map(|event| event.kind);
+
+    for (mut text, mut display) in &mut texts {
+        if let Some(kind) = resisted {
+            text.sections[0].value = format!("Resisted: {}", kind.name());
+            display.timer.reset();
+        } else if display.timer.tick(time.delta()).just_finished() {
+            text.sections[0].value.clear();
+        }
     }
 }
 
@@ -95,3 +401,36 @@ fn update_score(mut texts: Query<&mut Text, With<ScoreDisplay>>, score: Res<Scor
         text.sections[0].value = format!("Score: {:.1}", score.score);
     }
 }
+
+/// Show which wave is coming up while it's being announced.
+fn update_wave_banner(
+    mut banners: Query<(&mut Text, &mut Visibility), With<WaveBanner>>,
+    wave: Res<Wave>,
+) {
+    for (mut text, mut visibility) in &mut banners {
+        text.sections[0].value = format!("Wave {}", wave.number);
+        visibility.set_if_neq(if wave.is_announcing() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+fn update_replay_ui(
+    mut texts: Query<&mut Text, With<ReplayDisplay>>,
+    playback: Res<State<PlaybackState>>,
+    bindings: Res<InputBindings>,
+) {
+    let fast_forward_key = bindings.key_name(InputAction::FastForward);
+
+    for mut text in &mut texts {
+        text.sections[0].value = match playback.get() {
+            PlaybackState::Live => String::new(),
+            PlaybackState::Playing => format!("Replay [{fast_forward_key} to fast forward]"),
+            PlaybackState::FastForward => {
+                format!("Replay >> [{fast_forward_key} for normal speed]")
+            }
+        };
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{state::GameState, despawn::remove_with_component};
+use crate::{
+    despawn::remove_with_component,
+    input::{InputAction, InputBindings},
+    rng::GameSeed,
+    state::GameState,
+    wave::Wave,
+};
 use bevy::prelude::*;
 
 pub struct GameOverUiPlugin;
@@ -16,7 +22,12 @@ impl Plugin for GameOverUiPlugin {
 #[derive(Component)]
 struct GameOverUi;
 
-fn spawn_gameover_ui(mut commands: Commands) {
+fn spawn_gameover_ui(
+    mut commands: Commands,
+    seed: Res<GameSeed>,
+    wave: Res<Wave>,
+    bindings: Res<InputBindings>,
+) {
     commands
         .spawn((
             NodeBundle {
@@ -46,7 +57,32 @@ fn spawn_gameover_ui(mut commands: Commands) {
 
             parent.spawn((TextBundle {
                 text: Text::from_section(
-                    "[Press Space to Continue]",
+                    format!("Reached wave {}", wave.number),
+                    TextStyle {
+                        font_size: 32.0,
+                        ..default()
+                    },
+                ),
+                ..default()
+            },));
+
+            parent.spawn((TextBundle {
+                text: Text::from_section(
+                    format!("Seed: {}", seed.0),
+                    TextStyle {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                ),
+                ..default()
+            },));
+
+            parent.spawn((TextBundle {
+                text: Text::from_section(
+                    format!(
+                        "[Press {} to Continue]",
+                        bindings.key_name(InputAction::Confirm)
+                    ),
                     TextStyle {
                         font_size: 32.0,
                         ..default()
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::despawn::remove_with_component;
+use crate::input::{InputAction, InputBindings};
 use crate::state::GameState;
 
 pub struct PauseUiPlugin;
@@ -15,7 +16,7 @@ impl Plugin for PauseUiPlugin {
 #[derive(Component)]
 struct PauseUi;
 
-fn spawn_pause_ui(mut commands: Commands) {
+fn spawn_pause_ui(mut commands: Commands, bindings: Res<InputBindings>) {
     commands
         .spawn((
             NodeBundle {
@@ -45,7 +46,10 @@ fn spawn_pause_ui(mut commands: Commands) {
 
             parent.spawn((TextBundle {
                 text: Text::from_section(
-                    "[Press Space to Resume]",
+                    format!(
+                        "[Press {} to Resume]",
+                        bindings.key_name(InputAction::Pause)
+                    ),
                     TextStyle {
                         font_size: 32.0,
                         ..default()
@@ -56,7 +60,7 @@ fn spawn_pause_ui(mut commands: Commands) {
 
             parent.spawn((TextBundle {
                 text: Text::from_section(
-                    "[Press Q to Quit]",
+                    format!("[Press {} to Quit]", bindings.key_name(InputAction::Quit)),
                     TextStyle {
                         font_size: 28.0,
                         ..default()
@@ -1,4 +1,8 @@
-use crate::{state::GameState, despawn::remove_with_component};
+use crate::{
+    despawn::remove_with_component,
+    input::{InputAction, InputBindings},
+    state::GameState,
+};
 use bevy::prelude::*;
 
 pub struct StartUiPlugin;
@@ -13,7 +17,7 @@ impl Plugin for StartUiPlugin {
 #[derive(Component)]
 struct StartUi;
 
-fn spawn_start_ui(mut commands: Commands) {
+fn spawn_start_ui(mut commands: Commands, bindings: Res<InputBindings>) {
     commands
         .spawn((
             NodeBundle {
@@ -44,7 +48,10 @@ fn spawn_start_ui(mut commands: Commands) {
 
             parent.spawn((TextBundle {
                 text: Text::from_section(
-                    "[Press Space to Start]",
+                    format!(
+                        "[Press {} to Start]",
+                        bindings.key_name(InputAction::Confirm)
+                    ),
                     TextStyle {
                         font_size: 32.0,
                         ..default()
@@ -55,7 +62,7 @@ fn spawn_start_ui(mut commands: Commands) {
 
             parent.spawn((TextBundle {
                 text: Text::from_section(
-                    "[Press Q to Quit]",
+                    format!("[Press {} to Quit]", bindings.key_name(InputAction::Quit)),
                     TextStyle {
                         font_size: 28.0,
                         ..default()
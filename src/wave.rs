@@ -0,0 +1,155 @@
+use std::ops::Range;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asteroids::{Asteroid, AsteroidTuning},
+    movement::MovementSet,
+    schedule::InGameSet,
+    state::GameState,
+};
+
+/// Sends the asteroids in waves that get harder as the game goes on.  Each wave ends once every one of its asteroids,
+/// fragments and all, has been destroyed, then the next one is announced with a banner.
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaveTuning>()
+            .init_resource::<Wave>()
+            .add_systems(
+                OnTransition {
+                    from: GameState::Start,
+                    to: GameState::InGame,
+                },
+                announce_first_wave,
+            )
+            .add_systems(
+                FixedUpdate,
+                advance_waves
+                    .before(MovementSet)
+                    .in_set(InGameSet::EntityUpdates),
+            );
+    }
+}
+
+/// Balance numbers for waves, loaded from the tuning file.  See [crate::tuning].  The first wave uses the speed and
+/// health range from [AsteroidTuning], and every wave after it builds on those.
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WaveTuning {
+    pub first_wave_asteroids: u32,
+    pub extra_asteroids_per_wave: u32,
+    /// How much faster each wave's asteroids are than the first wave's, as a fraction of their speed.
+    pub speed_increase_per_wave: f32,
+    /// How much more health each wave's asteroids have than the first wave's, as a fraction of their health.
+    pub health_increase_per_wave: f32,
+    /// How long the banner shows before a wave starts.
+    pub banner_seconds: f32,
+}
+
+impl Default for WaveTuning {
+    fn default() -> Self {
+        Self {
+            first_wave_asteroids: 4,
+            extra_asteroids_per_wave: 2,
+            speed_increase_per_wave: 0.1,
+            health_increase_per_wave: 0.1,
+            banner_seconds: 3.0,
+        }
+    }
+}
+
+impl WaveTuning {
+    /// What the given wave, counting from 1, sends in.
+    pub fn definition(&self, number: u32, asteroids: &AsteroidTuning) -> WaveDefinition {
+        let waves_in = number.saturating_sub(1);
+        let speed = 1.0 + self.speed_increase_per_wave * waves_in as f32;
+        let health = 1.0 + self.health_increase_per_wave * waves_in as f32;
+
+        WaveDefinition {
+            asteroids: self.first_wave_asteroids + self.extra_asteroids_per_wave * waves_in,
+            velocity_scalar: asteroids.velocity_scalar * speed,
+            acceleration_scalar: asteroids.acceleration_scalar,
+            health_range: asteroids.health_range.start * health
+                ..asteroids.health_range.end * health,
+        }
+    }
+}
+
+/// How many asteroids a wave sends in, and how big and fast they are.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WaveDefinition {
+    pub asteroids: u32,
+    pub velocity_scalar: f32,
+    pub acceleration_scalar: f32,
+    pub health_range: Range<f32>,
+}
+
+/// The wave being played, or about to be.
+#[derive(Resource, Debug, Default)]
+pub struct Wave {
+    /// Which wave it is, counting from 1.
+    pub number: u32,
+    pub definition: WaveDefinition,
+    /// How many of the wave's asteroids have still to come in.
+    pub asteroids_to_spawn: u32,
+    /// Counts down while the wave is being announced, before any of its asteroids come in.
+    banner: Option<Timer>,
+}
+
+impl Wave {
+    fn announce(number: u32, tuning: &WaveTuning, asteroids: &AsteroidTuning) -> Self {
+        let definition = tuning.definition(number, asteroids);
+        Self {
+            number,
+            asteroids_to_spawn: definition.asteroids,
+            definition,
+            banner: Some(Timer::from_seconds(
+                tuning.banner_seconds.max(0.0),
+                TimerMode::Once,
+            )),
+        }
+    }
+
+    /// Whether the wave's banner is up, before it starts.
+    pub fn is_announcing(&self) -> bool {
+        self.banner.is_some()
+    }
+
+    /// Whether the wave has started and has asteroids left to send in.
+    pub fn is_spawning(&self) -> bool {
+        !self.is_announcing() && self.asteroids_to_spawn > 0
+    }
+}
+
+fn announce_first_wave(
+    mut wave: ResMut<Wave>,
+    tuning: Res<WaveTuning>,
+    asteroids: Res<AsteroidTuning>,
+) {
+    *wave = Wave::announce(1, &tuning, &asteroids);
+}
+
+/// Start a wave once its banner has been up long enough, and announce the next one once it's been cleared.
+fn advance_waves(
+    mut wave: ResMut<Wave>,
+    remaining: Query<(), With<Asteroid>>,
+    tuning: Res<WaveTuning>,
+    asteroids: Res<AsteroidTuning>,
+    time: Res<Time>,
+) {
+    if let Some(banner) = wave.banner.as_mut() {
+        if banner.tick(time.delta()).finished() {
+            wave.banner = None;
+            info!("Wave {} has started", wave.number);
+        }
+        return;
+    }
+
+    if wave.asteroids_to_spawn == 0 && remaining.is_empty() {
+        info!("Wave {} has been cleared", wave.number);
+        *wave = Wave::announce(wave.number + 1, &tuning, &asteroids);
+    }
+}
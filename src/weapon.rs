@@ -0,0 +1,449 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asset_loader::SceneAssets,
+    collision::{CollisionDamage, CollisionPush, CollisionTuning, Faction, FastProjectile},
+    despawn::{DespawnOnDie, DespawnTimer},
+    health::{DamageKind, Health},
+    movement::{
+        Acceleration, AngularVelocity, ConfinedToPlayArea, MovementSet, MovingObjectBundle,
+        Velocity,
+    },
+    schedule::InGameSet,
+};
+
+/// Steers homing projectiles.  Weapons themselves are fired by whoever holds them, see [fire_weapon].
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WeaponTuning>().add_systems(
+            FixedUpdate,
+            steer_homing_projectiles
+                .before(MovementSet)
+                .in_set(InGameSet::EntityUpdates),
+        );
+    }
+}
+
+/// The built-in weapons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WeaponKind {
+    SingleShot,
+    TripleSpread,
+    RapidFire,
+    PiercingBeam,
+    HomingMissile,
+}
+
+impl WeaponKind {
+    /// Every weapon, in the order they're cycled through.
+    pub const ALL: [WeaponKind; 5] = [
+        WeaponKind::SingleShot,
+        WeaponKind::TripleSpread,
+        WeaponKind::RapidFire,
+        WeaponKind::PiercingBeam,
+        WeaponKind::HomingMissile,
+    ];
+
+    /// The name shown to the player.
+    pub fn name(self) -> &'static str {
+        match self {
+            WeaponKind::SingleShot => "Single Shot",
+            WeaponKind::TripleSpread => "Triple Spread",
+            WeaponKind::RapidFire => "Rapid Fire",
+            WeaponKind::PiercingBeam => "Piercing Beam",
+            WeaponKind::HomingMissile => "Homing Missile",
+        }
+    }
+}
+
+/// How a weapon fires.  Each shot fires [Weapon::projectile_count] projectiles, fanned out evenly across
+/// [Weapon::spread].
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    pub projectile: ProjectileTemplate,
+    /// Shots per second while the trigger is held.
+    pub fire_rate: f32,
+    /// The angle in degrees between the outermost projectiles of a shot.
+    pub spread: f32,
+    pub projectile_count: u32,
+    /// The collision damage of each projectile.
+    pub damage: f32,
+    /// The score the player needs before they can use the weapon.
+    pub unlock_score: f32,
+}
+
+impl Weapon {
+    /// How long to wait between shots.  A weapon without a positive fire rate never fires.
+    pub fn cooldown(&self) -> Duration {
+        Duration::try_from_secs_f32(1.0 / self.fire_rate).unwrap_or(Duration::MAX)
+    }
+}
+
+/// What a weapon's projectiles look like and how they fly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectileTemplate {
+    pub model: ProjectileModel,
+    pub speed: f32,
+    pub lifespan_millis: u64,
+    /// How much damage the projectile takes before it breaks.  `inf` goes straight through everything.
+    pub health: f32,
+    pub radius: f32,
+    /// Projectiles longer than zero are capsules along the direction they fly.
+    pub length: f32,
+    /// How far in front of the shooter the projectile appears.
+    pub forward_offset: f32,
+    /// How fast, in radians per second, the projectile turns towards the nearest thing it can damage.  Zero flies
+    /// straight.
+    pub homing_turn_rate: f32,
+    /// How much momentum the projectile hands to whatever it hits.
+    pub push: f32,
+    /// What kind of damage the projectile does, which some [crate::health::Armor] resists better than others.
+    pub damage_kind: DamageKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProjectileModel {
+    Missile,
+    Beam,
+}
+
+/// Balance numbers for the built-in weapons, loaded from the tuning file.  See [crate::tuning].  A weapon given in
+/// the file replaces the built-in one completely.
+#[derive(Resource, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WeaponTuning {
+    pub single_shot: Weapon,
+    pub triple_spread: Weapon,
+    pub rapid_fire: Weapon,
+    pub piercing_beam: Weapon,
+    pub homing_missile: Weapon,
+}
+
+impl WeaponTuning {
+    pub fn get(&self, kind: WeaponKind) -> &Weapon {
+        match kind {
+            WeaponKind::SingleShot => &self.single_shot,
+            WeaponKind::TripleSpread => &self.triple_spread,
+            WeaponKind::RapidFire => &self.rapid_fire,
+            WeaponKind::PiercingBeam => &self.piercing_beam,
+            WeaponKind::HomingMissile => &self.homing_missile,
+        }
+    }
+}
+
+impl Default for WeaponTuning {
+    fn default() -> Self {
+        let missile = ProjectileTemplate {
+            model: ProjectileModel::Missile,
+            speed: 50.0,
+            lifespan_millis: 3000,
+            health: 2.5,
+            radius: 0.5,
+            length: 0.0,
+            forward_offset: 7.5,
+            homing_turn_rate: 0.0,
+            push: 200.0,
+            damage_kind: DamageKind::Kinetic,
+        };
+
+        Self {
+            single_shot: Weapon {
+                kind: WeaponKind::SingleShot,
+                projectile: missile.clone(),
+                fire_rate: 10.0,
+                spread: 0.0,
+                projectile_count: 1,
+                damage: 5.0,
+                unlock_score: 0.0,
+            },
+            triple_spread: Weapon {
+                kind: WeaponKind::TripleSpread,
+                projectile: missile.clone(),
+                fire_rate: 6.0,
+                spread: 30.0,
+                projectile_count: 3,
+                damage: 4.0,
+                unlock_score: 10.0,
+            },
+            rapid_fire: Weapon {
+                kind: WeaponKind::RapidFire,
+                projectile: ProjectileTemplate {
+                    speed: 65.0,
+                    lifespan_millis: 1500,
+                    health: 1.0,
+                    radius: 0.35,
+                    push: 40.0,
+                    ..missile.clone()
+                },
+                fire_rate: 25.0,
+                spread: 0.0,
+                projectile_count: 1,
+                damage: 2.0,
+                unlock_score: 20.0,
+            },
+            piercing_beam: Weapon {
+                kind: WeaponKind::PiercingBeam,
+                projectile: ProjectileTemplate {
+                    model: ProjectileModel::Beam,
+                    speed: 120.0,
+                    lifespan_millis: 600,
+                    health: f32::INFINITY,
+                    radius: 0.4,
+                    length: 6.0,
+                    push: 60.0,
+                    damage_kind: DamageKind::Energy,
+                    ..missile.clone()
+                },
+                fire_rate: 3.0,
+                spread: 0.0,
+                projectile_count: 1,
+                damage: 10.0,
+                unlock_score: 35.0,
+            },
+            homing_missile: Weapon {
+                kind: WeaponKind::HomingMissile,
+                projectile: ProjectileTemplate {
+                    speed: 30.0,
+                    lifespan_millis: 4000,
+                    radius: 0.6,
+                    homing_turn_rate: 3.0,
+                    damage_kind: DamageKind::Explosive,
+                    ..missile
+                },
+                fire_rate: 3.0,
+                spread: 40.0,
+                projectile_count: 2,
+                damage: 8.0,
+                unlock_score: 50.0,
+            },
+        }
+    }
+}
+
+/// The weapons an entity can switch between, and which one it's using.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Arsenal {
+    /// Always in the order of [WeaponKind::ALL].
+    unlocked: Vec<WeaponKind>,
+    selected: WeaponKind,
+}
+
+impl Arsenal {
+    /// An arsenal with just the one weapon.
+    pub fn new(weapon: WeaponKind) -> Self {
+        Self {
+            unlocked: vec![weapon],
+            selected: weapon,
+        }
+    }
+
+    pub fn selected(&self) -> WeaponKind {
+        self.selected
+    }
+
+    pub fn unlocked(&self) -> &[WeaponKind] {
+        &self.unlocked
+    }
+
+    /// Make a weapon available, returning whether it's new.
+    pub fn unlock(&mut self, weapon: WeaponKind) -> bool {
+        if self.unlocked.contains(&weapon) {
+            return false;
+        }
+
+        self.unlocked.push(weapon);
+        self.unlocked
+            .sort_by_key(|unlocked| WeaponKind::ALL.iter().position(|kind| kind == unlocked));
+        true
+    }
+
+    /// Switch to the next unlocked weapon, returning it.
+    pub fn cycle(&mut self) -> WeaponKind {
+        let index = self
+            .unlocked
+            .iter()
+            .position(|kind| *kind == self.selected)
+            .unwrap_or(0);
+        self.selected = self.unlocked[(index + 1) % self.unlocked.len()];
+        self.selected
+    }
+}
+
+/// Turns a projectile towards the nearest thing it can damage.
+#[derive(Component, Debug)]
+pub struct Homing {
+    /// Radians per second.
+    pub turn_rate: f32,
+}
+
+#[derive(Bundle)]
+pub struct ProjectileBundle {
+    pub moving_object_bundle: MovingObjectBundle,
+    pub collision_damage: CollisionDamage,
+    pub collision_push: CollisionPush,
+    pub fast_projectile: FastProjectile,
+    pub health: Health,
+    pub despawn_on_die: DespawnOnDie,
+    pub confined_to_play_area: ConfinedToPlayArea,
+    pub despawn_timer: DespawnTimer,
+}
+
+impl ProjectileBundle {
+    /// A projectile flying in the direction it's facing, which for models is along their local Z axis.
+    pub fn new(
+        model: Handle<Scene>,
+        transform: Transform,
+        template: &ProjectileTemplate,
+        faction: Faction,
+        damage: f32,
+    ) -> Self {
+        let collider = if template.length > 0.0 {
+            Collider::capsule_z(template.length / 2.0, template.radius)
+        } else {
+            Collider::ball(template.radius)
+        };
+
+        let moving_object_bundle = MovingObjectBundle::new(
+            SceneBundle {
+                scene: model,
+                transform,
+                ..Default::default()
+            },
+            collider,
+            faction,
+            -transform.forward() * template.speed,
+            Acceleration::new(Vec3::ZERO),
+            AngularVelocity::new(Vec3::ZERO),
+        );
+
+        ProjectileBundle {
+            moving_object_bundle,
+            collision_damage: CollisionDamage::new(damage).with_kind(template.damage_kind),
+            collision_push: CollisionPush::new(template.push),
+            fast_projectile: FastProjectile,
+            health: Health::new(template.health),
+            despawn_on_die: DespawnOnDie,
+            confined_to_play_area: ConfinedToPlayArea,
+            despawn_timer: DespawnTimer::new(Duration::from_millis(template.lifespan_millis)),
+        }
+    }
+}
+
+/// Fire one shot of a weapon from the shooter's transform, returning the projectiles so the caller can tag them.
+pub fn fire_weapon(
+    commands: &mut Commands,
+    assets: &SceneAssets,
+    shooter: &Transform,
+    weapon: &Weapon,
+    faction: Faction,
+) -> Vec<Entity> {
+    let template = &weapon.projectile;
+    let count = weapon.projectile_count.max(1);
+
+    (0..count)
+        .map(|i| {
+            // Fan the projectiles out evenly, with the middle of the fan straight ahead.
+            let angle = if count == 1 {
+                0.0
+            } else {
+                weapon.spread.to_radians() * (i as f32 / (count - 1) as f32 - 0.5)
+            };
+            let rotation = Quat::from_axis_angle(*shooter.up(), angle) * shooter.rotation;
+            let direction = rotation * Vec3::Z;
+
+            let transform = Transform::from_translation(
+                shooter.translation + direction * template.forward_offset,
+            )
+            .with_rotation(rotation);
+
+            let mut projectile = match template.model {
+                ProjectileModel::Missile => commands.spawn(ProjectileBundle::new(
+                    assets.missiles.clone(),
+                    transform,
+                    template,
+                    faction,
+                    weapon.damage,
+                )),
+                ProjectileModel::Beam => {
+                    let mut beam = commands.spawn(ProjectileBundle::new(
+                        Handle::default(),
+                        transform,
+                        template,
+                        faction,
+                        weapon.damage,
+                    ));
+                    beam.with_children(|builder| {
+                        builder.spawn(PbrBundle {
+                            mesh: assets.beam.clone(),
+                            material: assets.beam_material.clone(),
+                            transform: Transform::from_scale(Vec3::new(
+                                template.radius * 2.0,
+                                template.radius * 2.0,
+                                template.length + template.radius * 2.0,
+                            )),
+                            ..default()
+                        });
+                    });
+                    beam
+                }
+            };
+
+            if template.homing_turn_rate > 0.0 {
+                projectile.insert(Homing {
+                    turn_rate: template.homing_turn_rate,
+                });
+            }
+            projectile.id()
+        })
+        .collect()
+}
+
+/// Turn homing projectiles towards the nearest thing they can damage, keeping their speed.  What that is comes from
+/// the [CollisionTuning], so a player's missile goes after asteroids, saucers and their shots alike.
+fn steer_homing_projectiles(
+    mut projectiles: Query<(&mut Transform, &mut Velocity, &Homing, &Faction)>,
+    targets: Query<(&Transform, &Faction), (With<Health>, Without<Homing>)>,
+    collision_tuning: Res<CollisionTuning>,
+    time: Res<Time>,
+) {
+    for (mut transform, mut velocity, homing, faction) in projectiles.iter_mut() {
+        let position = transform.translation;
+        let Some(target) = targets
+            .iter()
+            .filter(|(_, target_faction)| collision_tuning.damages(*faction, **target_faction))
+            .map(|(target, _)| target.translation)
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+        else {
+            continue;
+        };
+
+        let speed = velocity.value.length();
+        let heading = velocity.value.normalize_or_zero();
+        let wanted = (target - position).normalize_or_zero();
+        if heading == Vec3::ZERO || wanted == Vec3::ZERO {
+            continue;
+        }
+
+        let max_turn = homing.turn_rate * time.delta_seconds();
+        let angle = heading.angle_between(wanted);
+        let heading = if angle <= max_turn {
+            wanted
+        } else {
+            Quat::IDENTITY.slerp(Quat::from_rotation_arc(heading, wanted), max_turn / angle)
+                * heading
+        };
+
+        velocity.value = heading * speed;
+        transform.rotation = Quat::from_rotation_arc(Vec3::Z, heading);
+    }
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    asteroids::{Asteroid, AsteroidTuning},
+    enemy::SaucerSize,
+    health::{Armor, DamageKind, Health, ResistedEvent},
+    weapon::{WeaponKind, WeaponTuning},
+};
+
+/// The armor of the asteroid type with the given name.
+fn armor(game: &TestGame, name: &str) -> Armor {
+    game.app
+        .world
+        .resource::<AsteroidTuning>()
+        .types
+        .iter()
+        .find(|asteroid_type| asteroid_type.name == name)
+        .unwrap()
+        .armor
+        .clone()
+}
+
+fn spawn_iron_asteroid(game: &mut TestGame, translation: Vec3, health: f32) -> Entity {
+    let armor = armor(game, "Iron");
+    let asteroid = game.spawn_asteroid(translation, Vec3::ZERO, health);
+    game.app.world.entity_mut(asteroid).insert(armor);
+    asteroid
+}
+
+fn damage(game: &TestGame, kind: WeaponKind) -> f32 {
+    game.app.world.resource::<WeaponTuning>().get(kind).damage
+}
+
+fn resisted(game: &mut TestGame) -> Vec<ResistedEvent> {
+    game.app
+        .world
+        .resource_mut::<Events<ResistedEvent>>()
+        .drain()
+        .collect()
+}
+
+#[test]
+fn iron_asteroids_shrug_off_small_shots() {
+    let mut game = TestGame::new();
+    let asteroid = spawn_iron_asteroid(&mut game, Vec3::new(30.0, 0.0, 30.0), 10.0);
+    game.spawn_projectile(
+        WeaponKind::RapidFire,
+        Vec3::new(30.0, 0.0, 30.0),
+        Vec3::ZERO,
+    );
+    game.ticks(2);
+
+    assert_eq!(game.health(asteroid), Some(10.0));
+}
+
+#[test]
+fn iron_asteroids_crack_under_explosives() {
+    let mut game = TestGame::new();
+    let iron = armor(&game, "Iron");
+    let damage = damage(&game, WeaponKind::HomingMissile);
+    let asteroid = spawn_iron_asteroid(&mut game, Vec3::new(30.0, 0.0, 30.0), 20.0);
+    game.spawn_projectile(
+        WeaponKind::HomingMissile,
+        Vec3::new(30.0, 0.0, 30.0),
+        Vec3::ZERO,
+    );
+    game.ticks(2);
+
+    let taken = 20.0 - game.health(asteroid).unwrap();
+    assert!(taken > damage);
+    assert_eq!(taken, iron.reduce(damage, DamageKind::Explosive));
+}
+
+#[test]
+fn large_saucers_resist_beams() {
+    let mut game = TestGame::new();
+    let damage = damage(&game, WeaponKind::PiercingBeam);
+    let saucer = game.spawn_saucer(SaucerSize::Large, Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO);
+    let health = game.health(saucer).unwrap();
+    game.spawn_projectile(
+        WeaponKind::PiercingBeam,
+        Vec3::new(30.0, 0.0, 30.0),
+        Vec3::ZERO,
+    );
+    game.ticks(2);
+
+    let taken = health - game.health(saucer).unwrap();
+    assert!(taken > 0.0);
+    assert!(taken < damage);
+}
+
+#[test]
+fn resisted_hits_are_reported() {
+    let mut game = TestGame::new();
+    let asteroid = spawn_iron_asteroid(&mut game, Vec3::new(30.0, 0.0, 30.0), 10.0);
+    let missile = game.spawn_missile(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO);
+    game.ticks(2);
+
+    let resisted = resisted(&mut game);
+    assert_eq!(resisted.len(), 1);
+    assert_eq!(resisted[0].source, Some(missile));
+    assert_eq!(resisted[0].target, asteroid);
+    assert_eq!(resisted[0].kind, DamageKind::Kinetic);
+}
+
+#[test]
+fn fragments_are_the_same_type_as_their_asteroid() {
+    let mut game = TestGame::new();
+    let iron = armor(&game, "Iron");
+    let asteroid = spawn_iron_asteroid(&mut game, Vec3::new(30.0, 0.0, 30.0), 10.0);
+    game.tick();
+
+    game.app.world.get_mut::<Health>(asteroid).unwrap().value = 0.0;
+    game.ticks(2);
+
+    let fragments: Vec<Armor> = game
+        .app
+        .world
+        .query_filtered::<&Armor, With<Asteroid>>()
+        .iter(&game.app.world)
+        .cloned()
+        .collect();
+    assert!(!fragments.is_empty());
+    assert!(fragments.iter().all(|armor| *armor == iron));
+}
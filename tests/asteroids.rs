@@ -0,0 +1,110 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    asteroids::{Asteroid, AsteroidTuning},
+    health::Health,
+    movement::Velocity,
+};
+
+/// Every asteroid in play, with its generation, health and velocity.
+fn asteroids(game: &mut TestGame) -> Vec<(u32, f32, Vec3)> {
+    game.app
+        .world
+        .query::<(&Asteroid, &Health, &Velocity)>()
+        .iter(&game.app.world)
+        .map(|(asteroid, health, velocity)| (asteroid.generation, health.value, velocity.value))
+        .collect()
+}
+
+/// Knock an asteroid's health out, and give the game the tick it dies on and the tick it's cleared up on.
+fn destroy(game: &mut TestGame, entity: Entity) {
+    game.app.world.get_mut::<Health>(entity).unwrap().value = 0.0;
+    game.ticks(2);
+}
+
+#[test]
+fn big_asteroid_splits_into_fragments() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(40.0, 0.0, 40.0), Vec3::ZERO, 20.0);
+    game.tick();
+
+    destroy(&mut game, asteroid);
+    assert!(!game.exists(asteroid));
+
+    let fragments = asteroids(&mut game);
+    assert!((2..=3).contains(&fragments.len()), "{fragments:?}");
+    for (generation, health, _) in fragments {
+        assert_eq!(generation, 1);
+        assert_eq!(health, 10.0);
+    }
+}
+
+#[test]
+fn fragments_fly_apart_but_keep_some_momentum() {
+    let mut game = TestGame::new();
+    let velocity = Vec3::new(10.0, 0.0, 0.0);
+    let asteroid = game.spawn_asteroid(Vec3::new(40.0, 0.0, 40.0), velocity, 20.0);
+    game.tick();
+
+    destroy(&mut game, asteroid);
+    let fragments = asteroids(&mut game);
+
+    // The fragments are spread evenly around the parent, so the divergence cancels out on average.
+    let momentum = game
+        .app
+        .world
+        .resource::<AsteroidTuning>()
+        .fragment_momentum;
+    let average = fragments
+        .iter()
+        .map(|(_, _, velocity)| *velocity)
+        .sum::<Vec3>()
+        / fragments.len() as f32;
+    assert!(average.distance(velocity * momentum) < 0.01, "{average}");
+
+    for (_, _, fragment_velocity) in &fragments {
+        assert!(fragment_velocity.distance(average) > 1.0);
+    }
+}
+
+#[test]
+fn small_asteroid_shatters() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(40.0, 0.0, 40.0), Vec3::ZERO, 5.0);
+    game.tick();
+
+    destroy(&mut game, asteroid);
+    assert!(!game.exists(asteroid));
+    assert!(asteroids(&mut game).is_empty());
+}
+
+#[test]
+fn every_generation_scores() {
+    let mut game = TestGame::new();
+    game.spawn_asteroid(Vec3::new(40.0, 0.0, 40.0), Vec3::ZERO, 20.0);
+    game.tick();
+
+    // Generations go 20, 10, then 5, which is too small to split again.
+    for generation in 0..3 {
+        let remaining: Vec<(Entity, u32)> = game
+            .app
+            .world
+            .query::<(Entity, &Asteroid)>()
+            .iter(&game.app.world)
+            .map(|(entity, asteroid)| (entity, asteroid.generation))
+            .collect();
+        assert!(!remaining.is_empty());
+
+        for (entity, fragment_generation) in remaining {
+            assert_eq!(fragment_generation, generation);
+            game.app.world.get_mut::<Health>(entity).unwrap().value = 0.0;
+        }
+        game.ticks(2);
+    }
+
+    assert!(asteroids(&mut game).is_empty());
+    assert_eq!(game.score(), game.deaths().len() as f32);
+    assert!(game.score() >= 7.0);
+}
@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use portal_asteroids::{
+    input::{BindingsError, InputAction, InputActionPlugin, InputBindings},
+    state::GameState,
+};
+
+/// Write a bindings file of this test's own, so tests running at the same time don't overwrite each other's.
+fn bindings_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "portal-asteroids-{name}-{}.bindings.ron",
+        std::process::id()
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// The bindings the game ends up with after starting up with this file.
+fn bindings_at_startup(path: PathBuf) -> InputBindings {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin))
+        .add_plugins(InputActionPlugin {
+            bindings_path: Some(path),
+        })
+        // Unplugging a gamepad pauses the game.
+        .init_state::<GameState>();
+    app.update();
+    app.world.resource::<InputBindings>().clone()
+}
+
+#[test]
+fn conflicting_bindings_fall_back_to_the_defaults() {
+    // Fire and Thrust are used at the same time, so can't share a key.
+    let path = bindings_file("conflict", "{ Fire: [KeyW] }");
+
+    assert!(matches!(
+        InputBindings::load(&path),
+        Err(BindingsError::Conflict {
+            key: KeyCode::KeyW,
+            ..
+        })
+    ));
+    assert_eq!(bindings_at_startup(path.clone()), InputBindings::default());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn a_malformed_file_falls_back_to_the_defaults() {
+    let path = bindings_file("malformed", "{ Fire: [Space ");
+
+    assert!(matches!(
+        InputBindings::load(&path),
+        Err(BindingsError::Parse(_))
+    ));
+    assert_eq!(bindings_at_startup(path.clone()), InputBindings::default());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn several_keys_can_be_bound_to_an_action() {
+    let path = bindings_file(
+        "several-keys",
+        "{ Fire: [Space, Enter, KeyJ], Shield: [KeyK, ShiftRight] }",
+    );
+
+    let bindings = bindings_at_startup(path.clone());
+    assert_eq!(
+        bindings.keys(InputAction::Fire),
+        [KeyCode::Space, KeyCode::Enter, KeyCode::KeyJ]
+    );
+    assert_eq!(
+        bindings.keys(InputAction::Shield),
+        [KeyCode::KeyK, KeyCode::ShiftRight]
+    );
+    // Actions left out of the file keep their defaults.
+    assert_eq!(
+        bindings.keys(InputAction::Thrust),
+        InputBindings::default().keys(InputAction::Thrust)
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
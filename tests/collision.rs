@@ -0,0 +1,76 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{spaceship::SpaceshipTuning, weapon::WeaponTuning};
+
+#[test]
+fn asteroid_and_spaceship_damage_each_other() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let spaceship_health = game.health(spaceship).unwrap();
+    let spaceship_damage = game
+        .app
+        .world
+        .resource::<SpaceshipTuning>()
+        .collision_damage;
+
+    // An asteroid's collision damage is the same as its health.
+    let asteroid_health = spaceship_damage + 10.0;
+    let asteroid = game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, asteroid_health);
+    game.tick();
+
+    assert_eq!(
+        game.health(spaceship),
+        Some(spaceship_health - asteroid_health)
+    );
+    assert_eq!(
+        game.health(asteroid),
+        Some(asteroid_health - spaceship_damage)
+    );
+    assert!(game.deaths().is_empty());
+}
+
+#[test]
+fn collisions_only_do_damage_once() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let spaceship_health = game.health(spaceship).unwrap();
+
+    game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, 100.0);
+    game.tick();
+    let damaged_health = game.health(spaceship).unwrap();
+    assert!(damaged_health < spaceship_health);
+
+    // Staying overlapped doesn't keep doing damage.
+    game.ticks(10);
+    assert_eq!(game.health(spaceship), Some(damaged_health));
+}
+
+#[test]
+fn missile_destroys_asteroid_for_a_point() {
+    let mut game = TestGame::new();
+    let damage = game.app.world.resource::<WeaponTuning>().single_shot.damage;
+
+    let asteroid = game.spawn_asteroid(Vec3::new(20.0, 0.0, 20.0), Vec3::ZERO, damage);
+    let missile = game.spawn_missile(Vec3::new(20.0, 0.0, 10.0), Vec3::new(0.0, 0.0, 30.0));
+    game.ticks(30);
+
+    assert!(game.deaths().contains(&asteroid));
+    assert!(game.deaths().contains(&missile));
+    assert!(!game.exists(asteroid));
+    assert!(!game.exists(missile));
+    assert_eq!(game.score(), 1.0);
+}
+
+#[test]
+fn missile_that_misses_does_no_damage() {
+    let mut game = TestGame::new();
+
+    let asteroid = game.spawn_asteroid(Vec3::new(20.0, 0.0, 20.0), Vec3::ZERO, 10.0);
+    game.spawn_missile(Vec3::new(-20.0, 0.0, 10.0), Vec3::new(0.0, 0.0, 30.0));
+    game.ticks(30);
+
+    assert_eq!(game.health(asteroid), Some(10.0));
+    assert_eq!(game.score(), 0.0);
+}
@@ -0,0 +1,296 @@
+//! A headless game for integration tests, with helpers for setting up scenarios and stepping through them.
+
+// Each test file only uses some of the helpers.
+#![allow(dead_code)]
+
+use std::{path::Path, time::Duration};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use portal_asteroids::{
+    asset_loader::SceneAssets,
+    asteroids::{Asteroid, AsteroidBundle, AsteroidTuning},
+    collision::Faction,
+    enemy::{SaucerBundle, SaucerSize, SaucerTuning},
+    headless::{headless_app, recording_app, set_headless_tick_rate},
+    health::{DieEvent, Health},
+    input::{InputAction, InputBindings},
+    movement::{TransformInterpolation, Velocity},
+    powerup::{PowerUpBundle, PowerUpKind, PowerUpTuning},
+    schedule::DEFAULT_TICK_RATE,
+    scoreboard::Scoreboard,
+    spaceship::{Spaceship, SpaceshipMissile},
+    state::GameState,
+    tuning::TuningSource,
+    wave::WaveTuning,
+    weapon::{ProjectileBundle, WeaponKind, WeaponTuning},
+};
+
+/// Asteroids and saucers only turn up when a test spawns them.
+const NEVER_SECONDS: f32 = 1_000_000.0;
+
+/// A game running headless, one fixed tick per [TestGame::tick].
+pub struct TestGame {
+    pub app: App,
+}
+
+impl TestGame {
+    /// A game that has just started, with the spaceship at its starting position.  Asteroids, saucers and power-ups
+    /// don't turn up on their own, so a test has the play area to itself.
+    pub fn new() -> Self {
+        Self::with_tick_rate(DEFAULT_TICK_RATE)
+    }
+
+    /// A game like [TestGame::new], stepped this many times a second instead.
+    pub fn with_tick_rate(tick_rate: f64) -> Self {
+        let mut app = headless_app(Some(0), TuningSource::Defaults);
+        set_headless_tick_rate(&mut app, tick_rate);
+        app.world
+            .resource_mut::<AsteroidTuning>()
+            .spawn_time_seconds = NEVER_SECONDS;
+        app.world.resource_mut::<SaucerTuning>().spawn_seconds = NEVER_SECONDS;
+        app.world.resource_mut::<PowerUpTuning>().drop_chance = 0.0;
+        Self::start(app)
+    }
+
+    /// A game recorded into a replay file when it ends.  Unlike [TestGame::new], asteroids and the rest turn up as
+    /// they would in a real game, since a replay can only play back what the game does by itself.
+    pub fn recording(path: &Path) -> Self {
+        Self::start(recording_app(
+            Some(0),
+            TuningSource::Defaults,
+            path.to_path_buf(),
+        ))
+    }
+
+    fn start(mut app: App) -> Self {
+        app.init_resource::<Deaths>()
+            .add_systems(FixedLast, record_deaths);
+
+        let mut game = Self { app };
+        // The first update starts the game, but time doesn't move on until the one after.
+        game.app.update();
+        assert_eq!(game.state(), GameState::InGame);
+        game
+    }
+
+    /// Advance the game by one tick.
+    pub fn tick(&mut self) {
+        self.app.update();
+    }
+
+    pub fn ticks(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// How many ticks it takes for a timer of this many seconds to run out, at the game's tick rate.
+    pub fn ticks_for(&self, seconds: f32) -> u32 {
+        let timestep = self.app.world.resource::<Time<Fixed>>().timestep();
+        Duration::from_secs_f32(seconds)
+            .as_nanos()
+            .div_ceil(timestep.as_nanos()) as u32
+    }
+
+    /// Tick until the wave banner has gone and the first wave has started.
+    pub fn skip_wave_banner(&mut self) {
+        let banner_seconds = self.app.world.resource::<WaveTuning>().banner_seconds;
+        self.ticks(self.ticks_for(banner_seconds) + 1);
+    }
+
+    /// One of the tuning resources, to change the numbers for a test.
+    pub fn tuning<T: Resource>(&mut self) -> Mut<'_, T> {
+        self.app.world.resource_mut::<T>()
+    }
+
+    pub fn spaceship(&mut self) -> Entity {
+        self.app
+            .world
+            .query_filtered::<Entity, With<Spaceship>>()
+            .single(&self.app.world)
+    }
+
+    /// Every asteroid in play, fragments included.
+    pub fn asteroids(&mut self) -> Vec<Entity> {
+        self.app
+            .world
+            .query_filtered::<Entity, With<Asteroid>>()
+            .iter(&self.app.world)
+            .collect()
+    }
+
+    pub fn spawn_asteroid(&mut self, translation: Vec3, velocity: Vec3, health: f32) -> Entity {
+        let model = self.app.world.resource::<SceneAssets>().asteroid.clone();
+        self.app
+            .world
+            .spawn(AsteroidBundle::new(
+                model,
+                translation,
+                velocity,
+                Vec3::ZERO,
+                Vec3::ZERO,
+                health,
+            ))
+            .id()
+    }
+
+    pub fn spawn_saucer(&mut self, size: SaucerSize, translation: Vec3, velocity: Vec3) -> Entity {
+        let tuning = self.app.world.resource::<SaucerTuning>().clone();
+        self.app
+            .world
+            .spawn(SaucerBundle::new(size, translation, velocity, &tuning))
+            .id()
+    }
+
+    pub fn spawn_power_up(&mut self, kind: PowerUpKind, translation: Vec3) -> Entity {
+        let tuning = self.app.world.resource::<PowerUpTuning>().clone();
+        self.app
+            .world
+            .spawn(PowerUpBundle::new(kind, translation, Vec3::ZERO, &tuning))
+            .id()
+    }
+
+    /// Spawn a missile as if the spaceship had just fired its single shot weapon, with the current [WeaponTuning].
+    pub fn spawn_missile(&mut self, translation: Vec3, velocity: Vec3) -> Entity {
+        self.spawn_projectile(WeaponKind::SingleShot, translation, velocity)
+    }
+
+    /// Spawn one of a weapon's projectiles as if the spaceship had just fired it, with the current [WeaponTuning].
+    pub fn spawn_projectile(
+        &mut self,
+        kind: WeaponKind,
+        translation: Vec3,
+        velocity: Vec3,
+    ) -> Entity {
+        let model = self.app.world.resource::<SceneAssets>().missiles.clone();
+        let weapon = self.app.world.resource::<WeaponTuning>().get(kind).clone();
+
+        let mut template = weapon.projectile;
+        template.speed = velocity.length();
+        let rotation = match velocity.try_normalize() {
+            Some(direction) => Quat::from_rotation_arc(Vec3::Z, direction),
+            None => Quat::IDENTITY,
+        };
+
+        self.app
+            .world
+            .spawn((
+                ProjectileBundle::new(
+                    model,
+                    Transform::from_translation(translation).with_rotation(rotation),
+                    &template,
+                    Faction::PlayerProjectile,
+                    weapon.damage,
+                ),
+                SpaceshipMissile,
+            ))
+            .id()
+    }
+
+    /// Move an entity straight to a position, and set it moving.
+    pub fn place(&mut self, entity: Entity, translation: Vec3, velocity: Vec3) {
+        let mut entity = self.app.world.entity_mut(entity);
+
+        let mut transform = entity.get_mut::<Transform>().unwrap();
+        transform.translation = translation;
+        let transform = *transform;
+
+        if let Some(mut interpolation) = entity.get_mut::<TransformInterpolation>() {
+            interpolation.reset(&transform);
+        }
+        entity.get_mut::<Velocity>().unwrap().value = velocity;
+    }
+
+    /// Where the last tick left an entity.  Its [Transform] is blended between ticks for rendering, so lags behind.
+    pub fn translation(&self, entity: Entity) -> Vec3 {
+        let entity = self.app.world.entity(entity);
+        match entity.get::<TransformInterpolation>() {
+            Some(interpolation) => interpolation.current().translation,
+            None => entity.get::<Transform>().unwrap().translation,
+        }
+    }
+
+    /// Start holding an action, through the first key bound to it.
+    pub fn press(&mut self, action: InputAction) {
+        let key = self.key(action);
+        self.app
+            .world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+    }
+
+    pub fn release(&mut self, action: InputAction) {
+        let key = self.key(action);
+        self.app
+            .world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+    }
+
+    /// Press and let go of an action, and give the game a tick to react to it.
+    pub fn tap(&mut self, action: InputAction) {
+        self.press(action);
+        self.tick();
+        self.release(action);
+        self.tick();
+    }
+
+    fn key(&self, action: InputAction) -> KeyCode {
+        self.app.world.resource::<InputBindings>().keys(action)[0]
+    }
+
+    pub fn exists(&self, entity: Entity) -> bool {
+        self.app.world.get_entity(entity).is_some()
+    }
+
+    /// An entity's health, or [None] if it's gone.
+    pub fn health(&self, entity: Entity) -> Option<f32> {
+        self.app
+            .world
+            .get::<Health>(entity)
+            .map(|health| health.value)
+    }
+
+    /// Every entity a [DieEvent] has been sent for so far.
+    pub fn deaths(&self) -> Vec<Entity> {
+        self.app
+            .world
+            .resource::<Deaths>()
+            .0
+            .iter()
+            .map(|event| event.entity)
+            .collect()
+    }
+
+    /// The [DieEvent] sent for an entity, if it's died.
+    pub fn death(&self, entity: Entity) -> Option<DieEvent> {
+        self.app
+            .world
+            .resource::<Deaths>()
+            .0
+            .iter()
+            .find(|event| event.entity == entity)
+            .cloned()
+    }
+
+    pub fn score(&self) -> f32 {
+        self.app.world.resource::<Scoreboard>().score
+    }
+
+    pub fn state(&self) -> GameState {
+        *self.app.world.resource::<State<GameState>>().get()
+    }
+
+    /// Whether the game has asked to close.
+    pub fn exit_requested(&self) -> bool {
+        !self.app.world.resource::<Events<AppExit>>().is_empty()
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+struct Deaths(Vec<DieEvent>);
+
+fn record_deaths(mut die_events: EventReader<DieEvent>, mut deaths: ResMut<Deaths>) {
+    deaths.0.extend(die_events.read().cloned());
+}
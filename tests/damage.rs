@@ -0,0 +1,78 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    enemy::SaucerSize,
+    health::{DamageEvent, DamageKind, Invulnerable},
+};
+
+fn damage(game: &mut TestGame, target: Entity, amount: f32) {
+    game.app.world.send_event(DamageEvent {
+        source: None,
+        target,
+        amount,
+        kind: DamageKind::Collision,
+    });
+    game.tick();
+}
+
+#[test]
+fn damage_events_take_health() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+
+    damage(&mut game, asteroid, 4.0);
+    assert_eq!(game.health(asteroid), Some(6.0));
+}
+
+#[test]
+fn invulnerable_entities_ignore_damage_events() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship);
+    game.app
+        .world
+        .entity_mut(spaceship)
+        .insert(Invulnerable::from_seconds(10.0));
+
+    damage(&mut game, spaceship, 50.0);
+    assert_eq!(game.health(spaceship), health);
+}
+
+#[test]
+fn missile_gets_the_kill() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 1.0);
+    let missile = game.spawn_missile(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO);
+    game.ticks(3);
+
+    let death = game.death(asteroid).unwrap();
+    assert_eq!(death.killer, Some(missile));
+    assert_eq!(death.cause, Some(DamageKind::Kinetic));
+    assert_eq!(game.score(), 1.0);
+}
+
+#[test]
+fn ramming_is_a_collision_kill() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let translation = game.translation(spaceship);
+    let asteroid = game.spawn_asteroid(translation, Vec3::ZERO, 1.0);
+    game.ticks(3);
+
+    let death = game.death(asteroid).unwrap();
+    assert_eq!(death.killer, Some(spaceship));
+    assert_eq!(death.cause, Some(DamageKind::Collision));
+}
+
+#[test]
+fn asteroids_destroyed_by_saucers_score_nothing() {
+    let mut game = TestGame::new();
+    let saucer = game.spawn_saucer(SaucerSize::Large, Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO);
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 1.0);
+    game.ticks(3);
+
+    assert_eq!(game.death(asteroid).unwrap().killer, Some(saucer));
+    assert_eq!(game.score(), 0.0);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{input::InputAction, spaceship::SpaceshipMissile, weapon::WeaponTuning};
+
+/// How many ticks a missile lives for with the current tuning.
+fn missile_lifespan_ticks(game: &TestGame) -> u32 {
+    let tuning = game.app.world.resource::<WeaponTuning>();
+    game.ticks_for(tuning.single_shot.projectile.lifespan_millis as f32 / 1000.0)
+}
+
+#[test]
+fn missile_despawns_when_its_timer_runs_out() {
+    let mut game = TestGame::new();
+    let lifespan = missile_lifespan_ticks(&game);
+
+    let missile = game.spawn_missile(Vec3::new(20.0, 0.0, 20.0), Vec3::ZERO);
+    game.ticks(lifespan - 5);
+    assert!(game.exists(missile));
+    assert!(game.deaths().is_empty());
+
+    game.ticks(10);
+    assert_eq!(game.deaths(), [missile]);
+    assert!(!game.exists(missile));
+}
+
+#[test]
+fn fired_missiles_despawn() {
+    let mut game = TestGame::new();
+    let lifespan = missile_lifespan_ticks(&game);
+
+    game.press(InputAction::Fire);
+    game.ticks(10);
+    game.release(InputAction::Fire);
+
+    let missiles = game
+        .app
+        .world
+        .query_filtered::<Entity, With<SpaceshipMissile>>()
+        .iter(&game.app.world)
+        .count();
+    assert!(missiles > 0);
+
+    game.ticks(lifespan + 5);
+    let missiles = game
+        .app
+        .world
+        .query_filtered::<Entity, With<SpaceshipMissile>>()
+        .iter(&game.app.world)
+        .count();
+    assert_eq!(missiles, 0);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    asteroids::AsteroidTuning,
+    director::{Director, DirectorTuning},
+    health::Health,
+};
+
+fn director(game: &TestGame) -> &Director {
+    game.app.world.resource::<Director>()
+}
+
+#[test]
+fn intensity_curve_blends_between_points_and_repeats() {
+    let tuning = DirectorTuning {
+        intensity_curve: vec![(0.0, 0.0), (10.0, 1.0), (20.0, 0.5)],
+        ..default()
+    };
+
+    assert_eq!(tuning.target_intensity(0.0), 0.0);
+    assert_eq!(tuning.target_intensity(5.0), 0.5);
+    assert_eq!(tuning.target_intensity(15.0), 0.75);
+    assert_eq!(tuning.target_intensity(25.0), 0.5);
+}
+
+#[test]
+fn calm_game_speeds_asteroids_up_and_a_hard_one_slows_them_down() {
+    let mut game = TestGame::new();
+    game.app
+        .world
+        .resource_mut::<AsteroidTuning>()
+        .spawn_time_seconds = 2.0;
+    game.app
+        .world
+        .resource_mut::<DirectorTuning>()
+        .intensity_curve = vec![(0.0, 0.5)];
+    game.skip_wave_banner();
+    assert!(director(&game).spawn_interval < 2.0);
+
+    // Badly hurt, and just hit.
+    let spaceship = game.spaceship();
+    game.app.world.get_mut::<Health>(spaceship).unwrap().value = 10.0;
+    game.ticks(2);
+    assert!(director(&game).intensity > 0.5);
+    assert!(director(&game).spawn_interval > 2.0);
+}
+
+#[test]
+fn destroying_asteroids_quickly_makes_them_tougher() {
+    let mut game = TestGame::new();
+    game.skip_wave_banner();
+    let toughness = director(&game).toughness;
+
+    for i in 0..10 {
+        let asteroid = game.spawn_asteroid(
+            Vec3::new(40.0, 0.0, -40.0 + i as f32 * 8.0),
+            Vec3::ZERO,
+            5.0,
+        );
+        game.tick();
+        game.app.world.get_mut::<Health>(asteroid).unwrap().value = 0.0;
+        game.ticks(2);
+    }
+
+    assert!(director(&game).kill_rate > 0.0);
+    assert!(director(&game).toughness > toughness);
+}
+
+#[test]
+fn asteroids_are_capped() {
+    let mut game = TestGame::new();
+    game.app
+        .world
+        .resource_mut::<AsteroidTuning>()
+        .spawn_time_seconds = 0.05;
+    game.app
+        .world
+        .resource_mut::<DirectorTuning>()
+        .max_asteroids = 2;
+    game.skip_wave_banner();
+
+    game.ticks(120);
+    assert_eq!(game.asteroids().len(), 2);
+    assert_eq!(director(&game).asteroids, 2);
+}
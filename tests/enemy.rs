@@ -0,0 +1,105 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    enemy::{Saucer, SaucerMissile, SaucerSize, SaucerTuning},
+    health::Health,
+    movement::Velocity,
+};
+
+fn saucers(game: &mut TestGame) -> Vec<Entity> {
+    game.app
+        .world
+        .query_filtered::<Entity, With<Saucer>>()
+        .iter(&game.app.world)
+        .collect()
+}
+
+/// Which way each of the saucers' missiles is flying.
+fn saucer_missiles(game: &mut TestGame) -> Vec<Vec3> {
+    game.app
+        .world
+        .query_filtered::<&Velocity, With<SaucerMissile>>()
+        .iter(&game.app.world)
+        .map(|velocity| velocity.value.normalize())
+        .collect()
+}
+
+#[test]
+fn saucer_flies_in_once_the_wave_has_started() {
+    let mut game = TestGame::new();
+    game.tuning::<SaucerTuning>().spawn_seconds = 1.0;
+
+    game.skip_wave_banner();
+    assert!(saucers(&mut game).is_empty());
+
+    game.ticks(game.ticks_for(1.0) + 1);
+    assert_eq!(saucers(&mut game).len(), 1);
+}
+
+#[test]
+fn only_one_saucer_at_a_time() {
+    let mut game = TestGame::new();
+    game.tuning::<SaucerTuning>().spawn_seconds = 0.5;
+    game.skip_wave_banner();
+
+    let mut seen = 0;
+    for _ in 0..game.ticks_for(5.0) {
+        game.tick();
+        let saucers = saucers(&mut game).len();
+        assert!(saucers <= 1);
+        seen = seen.max(saucers);
+    }
+    assert_eq!(seen, 1);
+}
+
+#[test]
+fn small_saucer_shoots_at_the_spaceship() {
+    let mut game = TestGame::new();
+    game.tuning::<SaucerTuning>().small.aim_error = 0.0;
+    let spaceship = game.spaceship();
+    let saucer = game.spawn_saucer(SaucerSize::Small, Vec3::new(40.0, 0.0, 20.0), Vec3::ZERO);
+
+    let fire_seconds = game.tuning::<SaucerTuning>().small.fire_seconds;
+    game.ticks(game.ticks_for(fire_seconds));
+
+    let missiles = saucer_missiles(&mut game);
+    assert_eq!(missiles.len(), 1);
+    let towards_spaceship = (game.translation(spaceship) - game.translation(saucer)).normalize();
+    assert!(missiles[0].dot(towards_spaceship) > 0.99);
+}
+
+#[test]
+fn large_saucer_shoots_wherever() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let saucer = game.spawn_saucer(SaucerSize::Large, Vec3::new(40.0, 0.0, 20.0), Vec3::ZERO);
+    let towards_spaceship = (game.translation(spaceship) - game.translation(saucer)).normalize();
+
+    let fire_seconds = game.tuning::<SaucerTuning>().large.fire_seconds;
+    let mut directions = Vec::new();
+    for _ in 0..5 {
+        game.ticks(game.ticks_for(fire_seconds));
+        directions.extend(saucer_missiles(&mut game));
+    }
+
+    assert!(!directions.is_empty());
+    assert!(directions
+        .iter()
+        .any(|direction| direction.dot(towards_spaceship) < 0.99));
+}
+
+#[test]
+fn destroying_a_saucer_awards_its_points() {
+    let mut game = TestGame::new();
+    let points = game.tuning::<SaucerTuning>().small.points;
+    let saucer = game.spawn_saucer(SaucerSize::Small, Vec3::new(40.0, 0.0, 20.0), Vec3::ZERO);
+    game.tick();
+
+    game.app.world.get_mut::<Health>(saucer).unwrap().value = 0.0;
+    game.ticks(2);
+
+    assert!(!game.exists(saucer));
+    assert_eq!(game.score(), points);
+}
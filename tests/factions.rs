@@ -0,0 +1,73 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    collision::{CollisionTuning, Faction},
+    powerup::PowerUpKind,
+};
+
+#[test]
+fn spaceship_is_not_hit_by_its_own_missiles() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship);
+    let translation = game.translation(spaceship);
+
+    let missile = game.spawn_missile(translation, Vec3::ZERO);
+    game.ticks(3);
+
+    assert_eq!(game.health(spaceship), health);
+    assert!(game.exists(missile));
+}
+
+#[test]
+fn asteroids_bounce_off_each_other_harmlessly() {
+    let mut game = TestGame::new();
+    let first = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+    let second = game.spawn_asteroid(Vec3::new(31.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+    game.ticks(3);
+
+    assert_eq!(game.health(first), Some(10.0));
+    assert_eq!(game.health(second), Some(10.0));
+}
+
+#[test]
+fn missiles_pass_through_power_ups() {
+    let mut game = TestGame::new();
+    let power_up = game.spawn_power_up(PowerUpKind::Repair, Vec3::new(30.0, 0.0, 30.0));
+    let missile = game.spawn_missile(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO);
+    game.ticks(3);
+
+    assert!(game.exists(power_up));
+    assert!(game.exists(missile));
+}
+
+#[test]
+fn missiles_still_hit_asteroids() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+    game.spawn_missile(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO);
+    game.ticks(3);
+
+    assert!(game.health(asteroid).unwrap() < 10.0);
+}
+
+#[test]
+fn interactions_can_be_tuned() {
+    let mut game = TestGame::new();
+    game.app
+        .world
+        .resource_mut::<CollisionTuning>()
+        .interactions
+        .retain(|pair| *pair != (Faction::Player, Faction::Hazard));
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship);
+    let translation = game.translation(spaceship);
+
+    let asteroid = game.spawn_asteroid(translation, Vec3::ZERO, 10.0);
+    game.ticks(3);
+
+    assert_eq!(game.health(spaceship), health);
+    assert_eq!(game.health(asteroid), Some(10.0));
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::CollisionEvent;
+use common::TestGame;
+use portal_asteroids::{
+    health::DamageKind, powerup::PowerUpKind, weapon::WeaponKind, weapon::WeaponTuning,
+};
+
+/// Few enough ticks a second that projectiles fly right past small asteroids between one tick and the next.
+const LOW_TICK_RATE: f64 = 5.0;
+
+/// A game at a low tick rate, with an asteroid of the given health that physics already knows about.
+fn game_with_asteroid(translation: Vec3, health: f32) -> (TestGame, Entity) {
+    let mut game = TestGame::with_tick_rate(LOW_TICK_RATE);
+    let asteroid = game.spawn_asteroid(translation, Vec3::ZERO, health);
+    game.tick();
+    (game, asteroid)
+}
+
+#[test]
+fn missile_hits_a_small_asteroid_it_flies_through_between_ticks() {
+    let (mut game, asteroid) = game_with_asteroid(Vec3::new(5.0, 0.0, 30.0), 1.0);
+    game.spawn_missile(Vec3::new(0.0, 0.0, 30.0), Vec3::new(50.0, 0.0, 0.0));
+    game.ticks(2);
+
+    assert!(game.deaths().contains(&asteroid));
+}
+
+#[test]
+fn swept_hits_are_credited_to_the_projectile() {
+    let (mut game, asteroid) = game_with_asteroid(Vec3::new(5.0, 0.0, 30.0), 1.0);
+    let missile = game.spawn_missile(Vec3::new(0.0, 0.0, 30.0), Vec3::new(50.0, 0.0, 0.0));
+    game.ticks(2);
+
+    let death = game.death(asteroid).unwrap();
+    assert_eq!(death.killer, Some(missile));
+    assert_eq!(death.cause, Some(DamageKind::Kinetic));
+}
+
+#[test]
+fn missile_misses_an_asteroid_it_flies_past() {
+    let (mut game, asteroid) = game_with_asteroid(Vec3::new(5.0, 0.0, 36.0), 1.0);
+    game.spawn_missile(Vec3::new(0.0, 0.0, 30.0), Vec3::new(50.0, 0.0, 0.0));
+    game.ticks(2);
+
+    assert_eq!(game.health(asteroid), Some(1.0));
+}
+
+#[test]
+fn beam_hits_everything_it_passes_through() {
+    let (mut game, first) = game_with_asteroid(Vec3::new(8.0, 0.0, 30.0), 5.0);
+    let second = game.spawn_asteroid(Vec3::new(15.0, 0.0, 30.0), Vec3::ZERO, 5.0);
+    game.tick();
+
+    game.spawn_projectile(
+        WeaponKind::PiercingBeam,
+        Vec3::new(0.0, 0.0, 30.0),
+        Vec3::new(120.0, 0.0, 0.0),
+    );
+    game.ticks(2);
+
+    assert!(game.deaths().contains(&first));
+    assert!(game.deaths().contains(&second));
+}
+
+#[test]
+fn hit_is_only_counted_once_when_the_projectile_ends_up_touching() {
+    let (mut game, asteroid) = game_with_asteroid(Vec3::new(24.0, 0.0, 30.0), 25.0);
+    let damage = game
+        .app
+        .world
+        .resource::<WeaponTuning>()
+        .piercing_beam
+        .damage;
+
+    game.spawn_projectile(
+        WeaponKind::PiercingBeam,
+        Vec3::new(0.0, 0.0, 30.0),
+        Vec3::new(120.0, 0.0, 0.0),
+    );
+    game.ticks(2);
+
+    assert_eq!(game.health(asteroid), Some(25.0 - damage));
+}
+
+#[test]
+fn sweep_skips_factions_that_pass_through_each_other() {
+    let mut game = TestGame::with_tick_rate(LOW_TICK_RATE);
+    let power_up = game.spawn_power_up(PowerUpKind::Repair, Vec3::new(5.0, 0.0, 30.0));
+    game.tick();
+
+    let mut reader = game
+        .app
+        .world
+        .resource::<Events<CollisionEvent>>()
+        .get_reader();
+    game.spawn_missile(Vec3::new(0.0, 0.0, 30.0), Vec3::new(50.0, 0.0, 0.0));
+    let mut touched = false;
+    for _ in 0..2 {
+        game.tick();
+        let events = game.app.world.resource::<Events<CollisionEvent>>();
+        touched |= reader.read(events).any(|event| match event {
+            CollisionEvent::Started(entity1, entity2, _) => {
+                *entity1 == power_up || *entity2 == power_up
+            }
+            CollisionEvent::Stopped(..) => false,
+        });
+    }
+
+    assert!(!touched);
+}
@@ -0,0 +1,15 @@
+use portal_asteroids::headless::run_headless;
+
+#[test]
+fn a_seeded_headless_run_plays_out_the_same_every_time() {
+    let first = run_headless(1200, Some(7));
+    let second = run_headless(1200, Some(7));
+
+    assert_eq!(first.ticks, 1200, "{first}");
+    assert_eq!(first, second);
+}
+
+#[test]
+fn the_seed_is_reported() {
+    assert_eq!(run_headless(1, Some(7)).seed, 7);
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    health::{DamageEvent, DamageKind, HealEvent, Health},
+    spaceship::SpaceshipTuning,
+};
+
+fn heal(game: &mut TestGame, target: Entity, amount: f32, overheal: f32) {
+    game.app.world.send_event(HealEvent {
+        target,
+        amount,
+        overheal,
+    });
+}
+
+fn damage(game: &mut TestGame, target: Entity, amount: f32) {
+    game.app.world.send_event(DamageEvent {
+        source: None,
+        target,
+        amount,
+        kind: DamageKind::Collision,
+    });
+}
+
+fn max_health(game: &TestGame, entity: Entity) -> f32 {
+    game.app.world.get::<Health>(entity).unwrap().max
+}
+
+#[test]
+fn healing_stops_at_max_health() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+    damage(&mut game, asteroid, 4.0);
+    game.tick();
+
+    heal(&mut game, asteroid, 3.0, 0.0);
+    game.tick();
+    assert_eq!(game.health(asteroid), Some(9.0));
+
+    heal(&mut game, asteroid, 3.0, 0.0);
+    game.tick();
+    assert_eq!(game.health(asteroid), Some(max_health(&game, asteroid)));
+}
+
+#[test]
+fn overhealing_goes_past_max_health_and_stays() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+
+    heal(&mut game, asteroid, 20.0, 5.0);
+    game.tick();
+    assert_eq!(game.health(asteroid), Some(15.0));
+
+    // Healing that doesn't allow overhealing doesn't take any away either.
+    heal(&mut game, asteroid, 1.0, 0.0);
+    game.tick();
+    assert_eq!(game.health(asteroid), Some(15.0));
+}
+
+#[test]
+fn healing_does_not_save_a_killing_blow() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+
+    damage(&mut game, asteroid, 10.0);
+    heal(&mut game, asteroid, 10.0, 0.0);
+    game.ticks(2);
+
+    assert!(game.deaths().contains(&asteroid));
+}
+
+#[test]
+fn spaceship_regenerates_after_going_without_damage() {
+    let mut game = TestGame::new();
+    let tuning = game.app.world.resource::<SpaceshipTuning>().clone();
+    let spaceship = game.spaceship();
+    damage(&mut game, spaceship, 50.0);
+    game.tick();
+    let damaged = game.health(spaceship).unwrap();
+
+    game.ticks(game.ticks_for(tuning.regeneration_delay_seconds) - 1);
+    assert_eq!(game.health(spaceship), Some(damaged));
+
+    game.ticks(game.ticks_for(1.0) + 1);
+    assert!(game.health(spaceship).unwrap() > damaged);
+
+    // It heals back up to full and no further.
+    game.ticks(game.ticks_for(50.0 / tuning.regeneration_per_second) + 1);
+    assert_eq!(game.health(spaceship), Some(tuning.health));
+}
+
+#[test]
+fn damage_restarts_the_regeneration_delay() {
+    let mut game = TestGame::new();
+    let tuning = game.app.world.resource::<SpaceshipTuning>().clone();
+    let spaceship = game.spaceship();
+    damage(&mut game, spaceship, 50.0);
+    game.ticks(game.ticks_for(tuning.regeneration_delay_seconds) - 10);
+
+    damage(&mut game, spaceship, 10.0);
+    game.tick();
+    let damaged = game.health(spaceship).unwrap();
+
+    game.ticks(game.ticks_for(tuning.regeneration_delay_seconds) - 1);
+    assert_eq!(game.health(spaceship), Some(damaged));
+}
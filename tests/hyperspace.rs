@@ -0,0 +1,122 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    hyperspace::{HyperspaceDrive, HyperspaceJump, HyperspaceTuning},
+    input::InputAction,
+    movement::Velocity,
+};
+
+fn is_jumping(game: &TestGame, spaceship: Entity) -> bool {
+    game.app.world.get::<HyperspaceJump>(spaceship).is_some()
+}
+
+#[test]
+fn jump_moves_the_spaceship_somewhere_safe() {
+    let mut game = TestGame::new();
+    game.tuning::<HyperspaceTuning>().malfunction_chance = 0.0;
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship).unwrap();
+    let start = game.translation(spaceship);
+    game.place(spaceship, start, Vec3::new(10.0, 0.0, 0.0));
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 10.0);
+
+    game.tap(InputAction::Hyperspace);
+    assert!(is_jumping(&game, spaceship));
+    assert_eq!(
+        game.app.world.get::<Velocity>(spaceship).unwrap().value,
+        Vec3::ZERO
+    );
+
+    let jump_seconds = game.tuning::<HyperspaceTuning>().jump_seconds;
+    game.ticks(game.ticks_for(jump_seconds));
+    let end = game.translation(spaceship);
+    assert_ne!(end, start);
+    let safe_radius = game.tuning::<HyperspaceTuning>().safe_radius;
+    assert!(end.distance(game.translation(asteroid)) >= safe_radius);
+    assert_eq!(game.health(spaceship), Some(health));
+}
+
+#[test]
+fn spaceship_cant_be_hit_while_away() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship).unwrap();
+
+    game.press(InputAction::Hyperspace);
+    game.tick();
+    game.release(InputAction::Hyperspace);
+    game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, 10.0);
+    game.ticks(3);
+
+    assert!(is_jumping(&game, spaceship));
+    assert_eq!(game.health(spaceship), Some(health));
+}
+
+#[test]
+fn malfunction_damages_the_spaceship() {
+    let mut game = TestGame::new();
+    game.tuning::<HyperspaceTuning>().malfunction_chance = 1.0;
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship).unwrap();
+
+    game.tap(InputAction::Hyperspace);
+    let jump_seconds = game.tuning::<HyperspaceTuning>().jump_seconds;
+    game.ticks(game.ticks_for(jump_seconds));
+
+    let damage = game.tuning::<HyperspaceTuning>().malfunction_damage;
+    assert_eq!(game.health(spaceship), Some(health - damage));
+}
+
+#[test]
+fn spaceship_cant_move_until_it_has_reentered() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+
+    game.tap(InputAction::Hyperspace);
+    game.press(InputAction::Thrust);
+    game.ticks(5);
+    assert_eq!(
+        game.app.world.get::<Velocity>(spaceship).unwrap().value,
+        Vec3::ZERO
+    );
+
+    let tuning = game.tuning::<HyperspaceTuning>().clone();
+    game.ticks(game.ticks_for(tuning.jump_seconds + tuning.reentry_seconds));
+    assert!(!is_jumping(&game, spaceship));
+    game.tick();
+    assert_ne!(
+        game.app.world.get::<Velocity>(spaceship).unwrap().value,
+        Vec3::ZERO
+    );
+}
+
+#[test]
+fn drive_has_to_recharge_between_jumps() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+
+    game.tap(InputAction::Hyperspace);
+    let tuning = game.tuning::<HyperspaceTuning>().clone();
+    game.ticks(game.ticks_for(tuning.jump_seconds + tuning.reentry_seconds));
+    assert!(!game
+        .app
+        .world
+        .get::<HyperspaceDrive>(spaceship)
+        .unwrap()
+        .is_ready());
+
+    game.tap(InputAction::Hyperspace);
+    assert!(!is_jumping(&game, spaceship));
+
+    game.ticks(game.ticks_for(tuning.cooldown_seconds));
+    assert!(game
+        .app
+        .world
+        .get::<HyperspaceDrive>(spaceship)
+        .unwrap()
+        .is_ready());
+    game.tap(InputAction::Hyperspace);
+    assert!(is_jumping(&game, spaceship));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::movement::{AngularVelocity, Velocity};
+
+fn velocity(game: &TestGame, entity: Entity) -> Vec3 {
+    game.app.world.get::<Velocity>(entity).unwrap().value
+}
+
+fn spin(game: &TestGame, entity: Entity) -> f32 {
+    game.app
+        .world
+        .get::<AngularVelocity>(entity)
+        .unwrap()
+        .value
+        .y
+}
+
+/// How fast the spaceship is knocked back by an asteroid flying into it at a given speed.
+fn spaceship_knockback(speed: f32) -> f32 {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let translation = game.translation(spaceship);
+    game.spawn_asteroid(
+        translation + Vec3::new(12.0, 0.0, 0.0),
+        Vec3::new(-speed, 0.0, 0.0),
+        10.0,
+    );
+    game.ticks(30);
+
+    -velocity(&game, spaceship).x
+}
+
+#[test]
+fn asteroids_bounce_off_each_other() {
+    let mut game = TestGame::new();
+    let first = game.spawn_asteroid(Vec3::new(15.0, 0.0, 30.0), Vec3::new(10.0, 0.0, 0.0), 10.0);
+    let second = game.spawn_asteroid(Vec3::new(35.0, 0.0, 30.0), Vec3::new(-10.0, 0.0, 0.0), 10.0);
+    game.ticks(60);
+
+    assert!(velocity(&game, first).x < 0.0);
+    assert!(velocity(&game, second).x > 0.0);
+    assert_eq!(game.health(first), Some(10.0));
+    assert_eq!(game.health(second), Some(10.0));
+}
+
+#[test]
+fn heavier_asteroids_are_knocked_back_less() {
+    let mut game = TestGame::new();
+    let small = game.spawn_asteroid(Vec3::new(10.0, 0.0, 30.0), Vec3::new(10.0, 0.0, 0.0), 5.0);
+    let big = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 30.0);
+    game.ticks(60);
+
+    let small_change = (velocity(&game, small) - Vec3::new(10.0, 0.0, 0.0)).length();
+    let big_change = velocity(&game, big).length();
+    assert!(big_change > 0.0);
+    assert!(big_change < small_change);
+}
+
+#[test]
+fn spaceship_is_knocked_back_by_how_hard_it_was_hit() {
+    let gentle = spaceship_knockback(10.0);
+    let hard = spaceship_knockback(30.0);
+
+    assert!(gentle > 0.0);
+    assert!(hard > gentle * 2.0);
+}
+
+#[test]
+fn missiles_give_a_small_push() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO, 20.0);
+    game.spawn_missile(Vec3::new(20.0, 0.0, 30.0), Vec3::new(50.0, 0.0, 0.0));
+    game.ticks(30);
+
+    let pushed = velocity(&game, asteroid);
+    assert!(pushed.x > 0.0);
+    assert!(pushed.length() < 5.0);
+}
+
+#[test]
+fn glancing_blows_set_asteroids_spinning() {
+    let mut game = TestGame::new();
+    let first = game.spawn_asteroid(Vec3::new(10.0, 0.0, 30.0), Vec3::new(15.0, 0.0, 0.0), 10.0);
+    let second = game.spawn_asteroid(Vec3::new(30.0, 0.0, 36.0), Vec3::ZERO, 10.0);
+    game.ticks(60);
+
+    assert_ne!(spin(&game, first), 0.0);
+    assert_ne!(spin(&game, second), 0.0);
+}
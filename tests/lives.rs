@@ -0,0 +1,116 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    health::{Health, Invulnerable},
+    spaceship::{Lives, Spaceship, SpaceshipTuning},
+    state::GameState,
+};
+
+/// How many ticks the spaceship waits to respawn with the current tuning.
+fn respawn_delay_ticks(game: &TestGame) -> u32 {
+    game.ticks_for(
+        game.app
+            .world
+            .resource::<SpaceshipTuning>()
+            .respawn_delay_seconds,
+    )
+}
+
+fn lives(game: &TestGame) -> &Lives {
+    game.app.world.resource::<Lives>()
+}
+
+fn spaceships(game: &mut TestGame) -> usize {
+    game.app
+        .world
+        .query_filtered::<Entity, With<Spaceship>>()
+        .iter(&game.app.world)
+        .count()
+}
+
+fn destroy_spaceship(game: &mut TestGame) {
+    let spaceship = game.spaceship();
+    game.app.world.get_mut::<Health>(spaceship).unwrap().value = 0.0;
+    game.ticks(2);
+    assert!(!game.exists(spaceship));
+}
+
+#[test]
+fn spaceship_respawns_after_a_delay() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let start = game.translation(spaceship);
+    let starting_lives = lives(&game).remaining;
+
+    game.place(spaceship, Vec3::new(30.0, 0.0, 30.0), Vec3::ZERO);
+    destroy_spaceship(&mut game);
+    assert_eq!(lives(&game).remaining, starting_lives - 1);
+    assert!(lives(&game).is_respawning());
+    assert_eq!(game.state(), GameState::InGame);
+
+    game.ticks(respawn_delay_ticks(&game) - 5);
+    assert_eq!(spaceships(&mut game), 0);
+
+    game.ticks(10);
+    assert!(!lives(&game).is_respawning());
+    let spaceship = game.spaceship();
+    assert_eq!(game.translation(spaceship), start);
+}
+
+#[test]
+fn respawn_waits_for_the_starting_position_to_clear() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let start = game.translation(spaceship);
+    destroy_spaceship(&mut game);
+
+    let asteroid = game.spawn_asteroid(start, Vec3::ZERO, 10.0);
+    game.ticks(respawn_delay_ticks(&game) + 10);
+    assert_eq!(spaceships(&mut game), 0);
+
+    game.app.world.despawn(asteroid);
+    game.ticks(2);
+    assert_eq!(spaceships(&mut game), 1);
+}
+
+#[test]
+fn respawned_spaceship_is_briefly_invulnerable() {
+    let mut game = TestGame::new();
+    destroy_spaceship(&mut game);
+    game.ticks(respawn_delay_ticks(&game) + 2);
+
+    let spaceship = game.spaceship();
+    assert!(game.app.world.get::<Invulnerable>(spaceship).is_some());
+    let health = game.health(spaceship).unwrap();
+
+    game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, 10.0);
+    game.ticks(3);
+    assert_eq!(game.health(spaceship), Some(health));
+
+    let invulnerable_seconds = game
+        .app
+        .world
+        .resource::<SpaceshipTuning>()
+        .invulnerable_seconds;
+    game.ticks(game.ticks_for(invulnerable_seconds));
+    assert!(game.app.world.get::<Invulnerable>(spaceship).is_none());
+}
+
+#[test]
+fn game_over_when_the_lives_run_out() {
+    let mut game = TestGame::new();
+    let starting_lives = lives(&game).remaining;
+
+    for _ in 1..starting_lives {
+        destroy_spaceship(&mut game);
+        game.ticks(respawn_delay_ticks(&game) + 2);
+        assert_eq!(game.state(), GameState::InGame);
+    }
+
+    destroy_spaceship(&mut game);
+    game.tick();
+    assert_eq!(lives(&game).remaining, 0);
+    assert_eq!(game.state(), GameState::GameOver);
+}
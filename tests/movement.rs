@@ -0,0 +1,53 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::movement::MovementTuning;
+
+#[test]
+fn spaceship_wraps_around_the_play_area() {
+    let mut game = TestGame::new();
+    let world_size = game.app.world.resource::<MovementTuning>().world_size;
+    let spaceship = game.spaceship();
+
+    game.place(
+        spaceship,
+        Vec3::new(0.0, 0.0, world_size - 0.5),
+        Vec3::new(0.0, 0.0, 60.0),
+    );
+    game.tick();
+
+    // Out the far side and back in on the near one.
+    let translation = game.translation(spaceship);
+    assert!(translation.z < -world_size + 1.0, "{translation}");
+    assert!(translation.length() <= world_size + 0.1, "{translation}");
+}
+
+#[test]
+fn asteroids_outside_the_play_area_are_not_wrapped() {
+    let mut game = TestGame::new();
+    let world_size = game.app.world.resource::<MovementTuning>().world_size;
+
+    let start = Vec3::new(world_size + 10.0, 0.0, 0.0);
+    let asteroid = game.spawn_asteroid(start, Vec3::new(30.0, 0.0, 0.0), 10.0);
+    game.ticks(10);
+
+    assert!(game.translation(asteroid).x > start.x);
+}
+
+#[test]
+fn asteroids_wrap_once_inside_the_play_area() {
+    let mut game = TestGame::new();
+    let world_size = game.app.world.resource::<MovementTuning>().world_size;
+
+    // Fly across the whole play area and out the other side.
+    let asteroid = game.spawn_asteroid(
+        Vec3::new(-world_size - 5.0, 0.0, 30.0),
+        Vec3::new(60.0, 0.0, 0.0),
+        10.0,
+    );
+    game.ticks(120);
+
+    let translation = game.translation(asteroid);
+    assert!(translation.length() <= world_size + 0.1, "{translation}");
+}
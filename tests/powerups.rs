@@ -0,0 +1,124 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    health::Health,
+    input::InputAction,
+    powerup::{ActivePowerUps, PowerUp, PowerUpKind, PowerUpTuning},
+    shield::Shield,
+    spaceship::SpaceshipTuning,
+};
+
+fn power_ups(game: &mut TestGame) -> Vec<Entity> {
+    game.app
+        .world
+        .query_filtered::<Entity, With<PowerUp>>()
+        .iter(&game.app.world)
+        .collect()
+}
+
+/// Fly the spaceship into a power-up.
+fn collect(game: &mut TestGame, kind: PowerUpKind) {
+    let spaceship = game.spaceship();
+    let translation = game.translation(spaceship);
+    let power_up = game.spawn_power_up(kind, translation);
+    game.ticks(2);
+    assert!(!game.exists(power_up));
+}
+
+fn is_active(game: &mut TestGame, kind: PowerUpKind) -> bool {
+    let spaceship = game.spaceship();
+    game.app
+        .world
+        .get::<ActivePowerUps>(spaceship)
+        .unwrap()
+        .is_active(kind)
+}
+
+#[test]
+fn destroyed_asteroid_drops_a_power_up_that_expires() {
+    let mut game = TestGame::new();
+    game.tuning::<PowerUpTuning>().drop_chance = 1.0;
+    let asteroid = game.spawn_asteroid(Vec3::new(40.0, 0.0, 40.0), Vec3::ZERO, 5.0);
+    game.tick();
+
+    game.app.world.get_mut::<Health>(asteroid).unwrap().value = 0.0;
+    game.ticks(2);
+    assert_eq!(power_ups(&mut game).len(), 1);
+
+    let lifespan_seconds = game.tuning::<PowerUpTuning>().lifespan_seconds;
+    game.ticks(game.ticks_for(lifespan_seconds) + 1);
+    assert!(power_ups(&mut game).is_empty());
+}
+
+#[test]
+fn repair_is_capped_at_full_health() {
+    let mut game = TestGame::new();
+    let full_health = game.app.world.resource::<SpaceshipTuning>().health;
+    let repair_amount = game.tuning::<PowerUpTuning>().repair_amount;
+    let spaceship = game.spaceship();
+
+    game.app.world.get_mut::<Health>(spaceship).unwrap().value = full_health - repair_amount * 2.0;
+    collect(&mut game, PowerUpKind::Repair);
+    assert_eq!(game.health(spaceship), Some(full_health - repair_amount));
+
+    collect(&mut game, PowerUpKind::Repair);
+    collect(&mut game, PowerUpKind::Repair);
+    assert_eq!(game.health(spaceship), Some(full_health));
+}
+
+#[test]
+fn shield_recharge_fills_the_shield() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    game.app.world.get_mut::<Shield>(spaceship).unwrap().energy = 0.0;
+
+    collect(&mut game, PowerUpKind::ShieldRecharge);
+    let shield = game.app.world.get::<Shield>(spaceship).unwrap();
+    assert_eq!(shield.energy, shield.max_energy);
+}
+
+#[test]
+fn timed_power_ups_wear_off() {
+    let mut game = TestGame::new();
+    collect(&mut game, PowerUpKind::RapidFire);
+    collect(&mut game, PowerUpKind::Multishot);
+    assert!(is_active(&mut game, PowerUpKind::RapidFire));
+    assert!(is_active(&mut game, PowerUpKind::Multishot));
+    assert!(!is_active(&mut game, PowerUpKind::ScoreMultiplier));
+
+    let effect_seconds = game.tuning::<PowerUpTuning>().effect_seconds;
+    game.ticks(game.ticks_for(effect_seconds));
+    assert!(!is_active(&mut game, PowerUpKind::RapidFire));
+    assert!(!is_active(&mut game, PowerUpKind::Multishot));
+}
+
+#[test]
+fn score_multiplier_multiplies_points() {
+    let mut game = TestGame::new();
+    let multiplier = game.tuning::<PowerUpTuning>().score_multiplier;
+    collect(&mut game, PowerUpKind::ScoreMultiplier);
+
+    let asteroid = game.spawn_asteroid(Vec3::new(40.0, 0.0, 40.0), Vec3::ZERO, 5.0);
+    game.tick();
+    game.app.world.get_mut::<Health>(asteroid).unwrap().value = 0.0;
+    game.ticks(2);
+
+    assert_eq!(game.score(), multiplier);
+}
+
+#[test]
+fn power_ups_left_floating_are_cleared_away_when_the_game_ends() {
+    let mut game = TestGame::new();
+    let power_up = game.spawn_power_up(PowerUpKind::Repair, Vec3::new(40.0, 0.0, 40.0));
+    game.tick();
+
+    game.tap(InputAction::Pause);
+    game.tap(InputAction::Quit);
+    assert!(!game.exists(power_up));
+
+    game.tap(InputAction::Confirm);
+    game.tap(InputAction::Confirm);
+    assert!(power_ups(&mut game).is_empty());
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use common::TestGame;
+use portal_asteroids::{
+    headless::verify_replay, input::InputAction, replay::Replay, state::GameState,
+};
+
+/// A replay file of this test's own, so tests running at the same time don't overwrite each other's.
+fn replay_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "portal-asteroids-{name}-{}.replay.ron",
+        std::process::id()
+    ))
+}
+
+/// Play a game with some shooting and turning in it, then quit, which saves the replay.
+fn record_game(path: &Path) -> Replay {
+    let mut game = TestGame::recording(path);
+    game.skip_wave_banner();
+
+    game.press(InputAction::Fire);
+    game.press(InputAction::RotateLeft);
+    game.ticks(game.ticks_for(20.0));
+    game.press(InputAction::Thrust);
+    game.release(InputAction::RotateLeft);
+    game.ticks(game.ticks_for(2.0));
+    game.release(InputAction::Thrust);
+    game.press(InputAction::RotateRight);
+    game.ticks(game.ticks_for(20.0));
+    game.release(InputAction::Fire);
+    game.release(InputAction::RotateRight);
+
+    game.tap(InputAction::Pause);
+    game.tap(InputAction::Quit);
+    assert_eq!(game.state(), GameState::GameOver);
+
+    // Something has to have happened for the replay to be worth checking.
+    let replay = Replay::load(path).unwrap();
+    assert!(replay.final_score > 0.0, "{}", replay.final_score);
+    assert_eq!(replay.final_score, game.score());
+    replay
+}
+
+#[test]
+fn a_recorded_game_plays_back_to_the_same_score() {
+    let path = replay_path("same-score");
+    let replay = record_game(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let verification = verify_replay(replay);
+    assert!(verification.matches(), "{verification}");
+}
+
+#[test]
+fn a_tampered_score_doesnt_match() {
+    let path = replay_path("tampered-score");
+    let mut replay = record_game(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    replay.final_score += 100.0;
+    let verification = verify_replay(replay);
+    assert!(verification.ended, "{verification}");
+    assert!(!verification.matches(), "{verification}");
+}
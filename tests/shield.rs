@@ -0,0 +1,92 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{input::InputAction, shield::Shield};
+
+fn shield(game: &TestGame, entity: Entity) -> Shield {
+    game.app.world.get::<Shield>(entity).unwrap().clone()
+}
+
+#[test]
+fn raised_shield_absorbs_collision_damage() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship).unwrap();
+
+    game.press(InputAction::Shield);
+    game.tick();
+    let energy = shield(&game, spaceship).energy;
+
+    game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, 10.0);
+    game.tick();
+
+    assert_eq!(game.health(spaceship), Some(health));
+    assert!(shield(&game, spaceship).energy <= energy - 10.0);
+}
+
+#[test]
+fn damage_gets_through_a_lowered_shield() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship).unwrap();
+
+    game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, 10.0);
+    game.tick();
+
+    assert_eq!(game.health(spaceship), Some(health - 10.0));
+    assert_eq!(shield(&game, spaceship).fraction(), 1.0);
+}
+
+#[test]
+fn damage_beyond_the_shield_energy_gets_through() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    let health = game.health(spaceship).unwrap();
+
+    game.press(InputAction::Shield);
+    game.tick();
+    let energy = shield(&game, spaceship).energy;
+
+    game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, energy + 15.0);
+    game.tick();
+
+    // The shield drains a little more on the tick it's hit.
+    let damage = health - game.health(spaceship).unwrap();
+    assert!((15.0..16.0).contains(&damage), "{damage}");
+    let shield = shield(&game, spaceship);
+    assert!(!shield.is_up());
+    assert!(shield.energy < 1.0);
+}
+
+#[test]
+fn shield_drains_while_up_and_recharges_when_down() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+
+    game.press(InputAction::Shield);
+    game.ticks(60);
+    let drained = shield(&game, spaceship);
+    assert!(drained.is_up());
+    assert!(drained.fraction() < 1.0);
+
+    game.release(InputAction::Shield);
+    game.ticks(60);
+    let recharged = shield(&game, spaceship);
+    assert!(!recharged.is_up());
+    assert!(recharged.energy > drained.energy);
+}
+
+#[test]
+fn empty_shield_drops_until_it_recharges() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+
+    game.press(InputAction::Shield);
+    game.ticks(60 * 10);
+    assert!(!shield(&game, spaceship).is_up());
+
+    // Holding the key doesn't bring it straight back up.
+    game.tick();
+    assert!(!shield(&game, spaceship).is_up());
+}
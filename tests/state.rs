@@ -0,0 +1,93 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{input::InputAction, spaceship::Lives, state::GameState};
+
+#[test]
+fn pausing_stops_the_game() {
+    let mut game = TestGame::new();
+    let asteroid = game.spawn_asteroid(Vec3::new(20.0, 0.0, 20.0), Vec3::new(10.0, 0.0, 0.0), 10.0);
+
+    game.tap(InputAction::Pause);
+    assert_eq!(game.state(), GameState::Paused);
+
+    let paused_at = game.translation(asteroid);
+    game.ticks(10);
+    assert_eq!(game.translation(asteroid), paused_at);
+
+    game.tap(InputAction::Pause);
+    assert_eq!(game.state(), GameState::InGame);
+    game.ticks(10);
+    assert_ne!(game.translation(asteroid), paused_at);
+}
+
+#[test]
+fn pausing_keeps_the_score() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+    game.spawn_asteroid(game.translation(spaceship), Vec3::ZERO, 1.0);
+    game.ticks(2);
+    assert_eq!(game.score(), 1.0);
+
+    game.tap(InputAction::Pause);
+    game.tap(InputAction::Pause);
+    assert_eq!(game.state(), GameState::InGame);
+    assert_eq!(game.score(), 1.0);
+}
+
+#[test]
+fn quitting_from_pause_ends_the_game() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+
+    game.tap(InputAction::Pause);
+    game.tap(InputAction::Quit);
+    assert_eq!(game.state(), GameState::GameOver);
+    assert!(!game.exists(spaceship));
+}
+
+#[test]
+fn game_over_when_the_last_spaceship_is_destroyed() {
+    let mut game = TestGame::new();
+    game.app.world.resource_mut::<Lives>().remaining = 1;
+    let spaceship = game.spaceship();
+    let spaceship_health = game.health(spaceship).unwrap();
+
+    game.spawn_asteroid(
+        game.translation(spaceship),
+        Vec3::ZERO,
+        spaceship_health + 1.0,
+    );
+    game.ticks(3);
+
+    assert!(game.deaths().contains(&spaceship));
+    assert_eq!(game.state(), GameState::GameOver);
+}
+
+#[test]
+fn continuing_from_game_over_goes_back_to_the_start() {
+    let mut game = TestGame::new();
+
+    game.tap(InputAction::Pause);
+    game.tap(InputAction::Quit);
+    game.tap(InputAction::Confirm);
+    assert_eq!(game.state(), GameState::Start);
+
+    game.tap(InputAction::Confirm);
+    assert_eq!(game.state(), GameState::InGame);
+    game.spaceship();
+}
+
+#[test]
+fn quitting_from_the_start_screen_exits() {
+    let mut game = TestGame::new();
+
+    game.tap(InputAction::Pause);
+    game.tap(InputAction::Quit);
+    game.tap(InputAction::Confirm);
+    assert!(!game.exit_requested());
+
+    game.tap(InputAction::Quit);
+    assert!(game.exit_requested());
+}
@@ -0,0 +1,176 @@
+use std::ops::Range;
+
+use portal_asteroids::tuning::{Tuning, TuningError};
+
+#[test]
+fn the_default_and_shipped_tuning_are_valid() {
+    Tuning::default().validate().unwrap();
+
+    let contents = std::fs::read_to_string("assets/tuning.ron").unwrap();
+    let shipped: Tuning = ron::from_str(&contents).unwrap();
+    shipped.validate().unwrap();
+}
+
+#[test]
+fn numbers_the_game_cant_use_are_turned_away() {
+    let mut tuning = Tuning::default();
+    tuning.hyperspace.jump_seconds = -1.0;
+    assert!(matches!(
+        tuning.validate(),
+        Err(TuningError::Invalid {
+            field: "hyperspace.jump_seconds",
+            ..
+        })
+    ));
+
+    let mut tuning = Tuning::default();
+    tuning.asteroids.spawn_time_seconds = f32::NAN;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.movement.world_size = 0.0;
+    assert!(tuning.validate().is_err());
+
+    // Too long to fit in a Duration.
+    let mut tuning = Tuning::default();
+    tuning.saucers.spawn_seconds = 1e20;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.power_ups.lifespan_seconds = 1e20;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.asteroids.health_range = 5.0..f32::INFINITY;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.asteroids.health_range = Range {
+        start: 20.0,
+        end: 5.0,
+    };
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.asteroids.types[0].weight = f32::NAN;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.asteroids.types[0].weight = -1.0;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    for asteroid_type in &mut tuning.asteroids.types {
+        asteroid_type.weight = 0.0;
+    }
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.asteroids.fragment_health_fraction = 1.0;
+    assert!(matches!(
+        tuning.validate(),
+        Err(TuningError::Invalid {
+            field: "asteroids.fragment_health_fraction",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn counts_the_game_cant_keep_up_with_are_turned_away() {
+    let mut tuning = Tuning::default();
+    tuning.asteroids.max_fragments = 4_000_000_000;
+    assert!(matches!(
+        tuning.validate(),
+        Err(TuningError::Invalid {
+            field: "asteroids.max_fragments",
+            ..
+        })
+    ));
+
+    let mut tuning = Tuning::default();
+    tuning.asteroids.min_fragments = 4;
+    tuning.asteroids.max_fragments = 2;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.director.max_asteroids = 0;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.director.max_asteroids = u32::MAX;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.weapons.triple_spread.projectile_count = u32::MAX;
+    assert!(matches!(
+        tuning.validate(),
+        Err(TuningError::Invalid {
+            field: "weapons.triple_spread.projectile_count",
+            ..
+        })
+    ));
+
+    // Fine on its own, but not once multishot adds to it.
+    let mut tuning = Tuning::default();
+    tuning.weapons.single_shot.projectile_count = 64;
+    assert!(tuning.validate().is_err());
+}
+
+#[test]
+fn bad_projectiles_collisions_and_intensity_curves_are_turned_away() {
+    let mut tuning = Tuning::default();
+    tuning.collisions.restitution = 1.5;
+    assert!(matches!(
+        tuning.validate(),
+        Err(TuningError::Invalid {
+            field: "collisions.restitution",
+            ..
+        })
+    ));
+
+    let mut tuning = Tuning::default();
+    tuning.weapons.rapid_fire.projectile.speed = f32::NAN;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.saucers.projectile.speed = -1.0;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.weapons.homing_missile.projectile.lifespan_millis = 0;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.weapons.piercing_beam.projectile.lifespan_millis = u64::MAX;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.director.intensity_curve.clear();
+    assert!(matches!(
+        tuning.validate(),
+        Err(TuningError::Invalid {
+            field: "director.intensity_curve",
+            ..
+        })
+    ));
+
+    let mut tuning = Tuning::default();
+    tuning.director.intensity_curve[1].1 = f32::NAN;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.director.intensity_curve[1].0 = f32::NAN;
+    assert!(tuning.validate().is_err());
+
+    let mut tuning = Tuning::default();
+    tuning.director.intensity_curve.reverse();
+    assert!(tuning.validate().is_err());
+}
+
+#[test]
+fn a_zero_fire_rate_is_allowed() {
+    let mut tuning = Tuning::default();
+    tuning.weapons.single_shot.fire_rate = 0.0;
+    tuning.validate().unwrap();
+}
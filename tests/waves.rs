@@ -0,0 +1,94 @@
+mod common;
+
+use common::TestGame;
+use portal_asteroids::{
+    asteroids::AsteroidTuning,
+    health::Health,
+    wave::{Wave, WaveTuning},
+};
+
+/// Have asteroids come in every few ticks, instead of never.
+fn spawn_quickly(game: &mut TestGame) {
+    game.app
+        .world
+        .resource_mut::<AsteroidTuning>()
+        .spawn_time_seconds = 0.05;
+}
+
+fn wave(game: &TestGame) -> &Wave {
+    game.app.world.resource::<Wave>()
+}
+
+/// Destroy every asteroid, fragments and all.
+fn clear_asteroids(game: &mut TestGame) {
+    for _ in 0..10 {
+        let asteroids = game.asteroids();
+        if asteroids.is_empty() {
+            return;
+        }
+        for asteroid in asteroids {
+            game.app.world.get_mut::<Health>(asteroid).unwrap().value = 0.0;
+        }
+        game.ticks(2);
+    }
+    panic!("the asteroids kept coming");
+}
+
+#[test]
+fn first_wave_is_announced_before_it_starts() {
+    let mut game = TestGame::new();
+    spawn_quickly(&mut game);
+    assert_eq!(wave(&game).number, 1);
+    assert!(wave(&game).is_announcing());
+
+    let banner_seconds = game.app.world.resource::<WaveTuning>().banner_seconds;
+    game.ticks(game.ticks_for(banner_seconds) - 5);
+    assert!(game.asteroids().is_empty());
+
+    game.ticks(30);
+    assert!(!wave(&game).is_announcing());
+    assert!(!game.asteroids().is_empty());
+}
+
+#[test]
+fn wave_sends_its_asteroids_then_stops() {
+    let mut game = TestGame::new();
+    spawn_quickly(&mut game);
+    let count = wave(&game).definition.asteroids;
+
+    game.skip_wave_banner();
+    game.ticks(count * 5 + 30);
+    assert_eq!(wave(&game).asteroids_to_spawn, 0);
+    assert_eq!(game.asteroids().len(), count as usize);
+}
+
+#[test]
+fn clearing_a_wave_announces_a_harder_one() {
+    let mut game = TestGame::new();
+    spawn_quickly(&mut game);
+    let first = wave(&game).definition.clone();
+
+    game.skip_wave_banner();
+    game.ticks(first.asteroids * 5 + 30);
+    clear_asteroids(&mut game);
+    game.tick();
+
+    assert_eq!(wave(&game).number, 2);
+    assert!(wave(&game).is_announcing());
+    let second = wave(&game).definition.clone();
+    assert!(second.asteroids > first.asteroids);
+    assert!(second.velocity_scalar > first.velocity_scalar);
+    assert!(second.health_range.start > first.health_range.start);
+    assert!(second.health_range.end > first.health_range.end);
+}
+
+#[test]
+fn wave_isnt_cleared_while_asteroids_are_still_to_come() {
+    let mut game = TestGame::new();
+    game.skip_wave_banner();
+    game.ticks(10);
+
+    // Nothing has spawned yet, since the test game never spawns asteroids on its own.
+    assert!(game.asteroids().is_empty());
+    assert_eq!(wave(&game).number, 1);
+}
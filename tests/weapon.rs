@@ -0,0 +1,166 @@
+mod common;
+
+use bevy::prelude::*;
+use common::TestGame;
+use portal_asteroids::{
+    enemy::SaucerSize,
+    input::InputAction,
+    movement::Velocity,
+    scoreboard::Scoreboard,
+    spaceship::SpaceshipMissile,
+    weapon::{Arsenal, Homing, Weapon, WeaponKind, WeaponTuning},
+};
+
+fn missiles(game: &mut TestGame) -> Vec<Entity> {
+    game.app
+        .world
+        .query_filtered::<Entity, With<SpaceshipMissile>>()
+        .iter(&game.app.world)
+        .collect()
+}
+
+fn weapon(game: &mut TestGame) -> WeaponKind {
+    let spaceship = game.spaceship();
+    game.app.world.get::<Weapon>(spaceship).unwrap().kind
+}
+
+fn unlock_everything(game: &mut TestGame) {
+    game.app.world.resource_mut::<Scoreboard>().score = 1000.0;
+    game.tick();
+}
+
+#[test]
+fn starts_with_just_the_single_shot() {
+    let mut game = TestGame::new();
+    let spaceship = game.spaceship();
+
+    assert_eq!(weapon(&mut game), WeaponKind::SingleShot);
+    game.tap(InputAction::CycleWeapon);
+    assert_eq!(weapon(&mut game), WeaponKind::SingleShot);
+
+    let arsenal = game.app.world.get::<Arsenal>(spaceship).unwrap();
+    assert_eq!(arsenal.unlocked(), [WeaponKind::SingleShot]);
+}
+
+#[test]
+fn weapons_unlock_with_score_and_cycle_in_order() {
+    let mut game = TestGame::new();
+    let triple_spread_score = game
+        .app
+        .world
+        .resource::<WeaponTuning>()
+        .triple_spread
+        .unlock_score;
+
+    game.app.world.resource_mut::<Scoreboard>().score = triple_spread_score;
+    game.tap(InputAction::CycleWeapon);
+    assert_eq!(weapon(&mut game), WeaponKind::TripleSpread);
+    game.tap(InputAction::CycleWeapon);
+    assert_eq!(weapon(&mut game), WeaponKind::SingleShot);
+
+    unlock_everything(&mut game);
+    let mut seen = vec![weapon(&mut game)];
+    for _ in 1..WeaponKind::ALL.len() {
+        game.tap(InputAction::CycleWeapon);
+        seen.push(weapon(&mut game));
+    }
+    assert_eq!(seen, WeaponKind::ALL);
+}
+
+#[test]
+fn triple_spread_fires_three_diverging_missiles() {
+    let mut game = TestGame::new();
+    unlock_everything(&mut game);
+    game.tap(InputAction::CycleWeapon);
+    assert_eq!(weapon(&mut game), WeaponKind::TripleSpread);
+
+    // Hold the trigger until the first shot goes off.
+    game.press(InputAction::Fire);
+    while missiles(&mut game).is_empty() {
+        game.tick();
+    }
+
+    let directions: Vec<Vec3> = missiles(&mut game)
+        .into_iter()
+        .map(|missile| {
+            game.app
+                .world
+                .get::<Velocity>(missile)
+                .unwrap()
+                .value
+                .normalize()
+        })
+        .collect();
+    assert_eq!(directions.len(), 3);
+    assert!(directions[0].angle_between(directions[1]) > 0.1);
+    assert!(directions[1].angle_between(directions[2]) > 0.1);
+}
+
+#[test]
+fn piercing_beam_goes_through_asteroids() {
+    let mut game = TestGame::new();
+    unlock_everything(&mut game);
+    for _ in 0..3 {
+        game.tap(InputAction::CycleWeapon);
+    }
+    assert_eq!(weapon(&mut game), WeaponKind::PiercingBeam);
+
+    let spaceship = game.spaceship();
+    let start = game.translation(spaceship);
+    // The spaceship starts facing up the screen.
+    let near = game.spawn_asteroid(start + Vec3::new(0.0, 0.0, 15.0), Vec3::ZERO, 5.0);
+    let far = game.spawn_asteroid(start + Vec3::new(0.0, 0.0, 30.0), Vec3::ZERO, 5.0);
+
+    game.press(InputAction::Fire);
+    while missiles(&mut game).is_empty() {
+        game.tick();
+    }
+    game.release(InputAction::Fire);
+    let beam = missiles(&mut game)[0];
+    game.ticks(20);
+
+    assert!(game.deaths().contains(&near));
+    assert!(game.deaths().contains(&far));
+    assert!(!game.deaths().contains(&beam));
+}
+
+#[test]
+fn a_zero_fire_rate_never_fires() {
+    let mut game = TestGame::new();
+    game.app
+        .world
+        .resource_mut::<WeaponTuning>()
+        .single_shot
+        .fire_rate = 0.0;
+
+    game.press(InputAction::Fire);
+    game.ticks(120);
+
+    assert!(missiles(&mut game).is_empty());
+}
+
+#[test]
+fn homing_missiles_go_after_saucers_as_well_as_asteroids() {
+    let mut game = TestGame::new();
+    let turn_rate = game
+        .tuning::<WeaponTuning>()
+        .homing_missile
+        .projectile
+        .homing_turn_rate;
+
+    // Flying straight past a saucer off to one side.
+    let missile = game.spawn_projectile(
+        WeaponKind::HomingMissile,
+        Vec3::new(-30.0, 0.0, 0.0),
+        Vec3::new(30.0, 0.0, 0.0),
+    );
+    game.app
+        .world
+        .entity_mut(missile)
+        .insert(Homing { turn_rate });
+    game.spawn_saucer(SaucerSize::Large, Vec3::new(-20.0, 0.0, 30.0), Vec3::ZERO);
+    game.ticks(10);
+
+    let velocity = game.app.world.get::<Velocity>(missile).unwrap().value;
+    assert!(velocity.z > 1.0, "{velocity}");
+}